@@ -41,6 +41,27 @@ fn largest<T>(list: &[T]) -> &T {
     _largest
 }
 
+// with the PartialOrd bound the comparison is allowed, and Copy lets us return the values themselves
+// instead of references. One pass over the list tracks both ends, and an empty list gives None
+// instead of panicking on list[0] like the functions above
+// (a NaN never compares as smaller or larger so it just gets skipped unless it is first)
+fn min_max<T: PartialOrd + Copy>(list: &[T]) -> Option<(T, T)> {
+    let (&first, rest) = list.split_first()?;
+    let mut min = first;
+    let mut max = first;
+
+    for &item in rest {
+        if item < min {
+            min = item;
+        }
+        if item > max {
+            max = item;
+        }
+    }
+
+    Some((min, max))
+}
+
 // we can also implement types to use generics 
 struct Point<T> {
     x: T,
@@ -100,6 +121,11 @@ fn main() {
     let result = largest(&char_list);
     println!("The largest Type T is {result}");
 
+    // both ends at once, handed back as a tuple
+    if let Some((min, max)) = min_max(&number_list) {
+        println!("The numbers go from {min} to {max}");
+    }
+
     // create a object of the struct with generics
     let integer = Point { x: 5, y: 10 };
     let float = Point { x: 1.0, y: 4.0 };
@@ -122,4 +148,39 @@ fn main() {
     let p3 = p1.mixup(p2);
 
     println!("p3.x = {}, p3.y = {}", p3.x, p3.y);
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn min_max_integers() {
+        assert_eq!(min_max(&[34, 50, 25, 100, 65]), Some((25, 100)));
+        assert_eq!(min_max(&[-3, -10, 0]), Some((-10, 0)));
+    }
+
+    #[test]
+    fn min_max_chars() {
+        assert_eq!(min_max(&['y', 'm', 'a', 'q']), Some(('a', 'y')));
+    }
+
+    #[test]
+    fn min_max_floats() {
+        assert_eq!(min_max(&[1.5, -0.25, 4.0, 3.75]), Some((-0.25, 4.0)));
+    }
+
+    #[test]
+    fn min_max_single_element() {
+        assert_eq!(min_max(&[7]), Some((7, 7)));
+        assert_eq!(min_max(&['z']), Some(('z', 'z')));
+        assert_eq!(min_max(&[2.5]), Some((2.5, 2.5)));
+    }
+
+    #[test]
+    fn min_max_empty() {
+        let empty: [i32; 0] = [];
+
+        assert_eq!(min_max(&empty), None);
+    }
+}
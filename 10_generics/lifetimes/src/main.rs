@@ -25,6 +25,24 @@ fn main() {
     let i = ImportantExcerpt {
         part: first_sentence,
     };
+
+    // the words come from novel not the parser, so they can outlive it
+    let first_two = {
+        let mut parser = Parser::new(&novel);
+        (parser.next_word(), parser.next_word())
+    };
+    println!("The first two words are {first_two:?}");
+
+    // two references from two different places
+    let title = String::from("Moby Dick");
+    {
+        let quote = String::from("Call me Ishmael");
+        let excerpt = DoubleExcerpt {
+            first: &title,
+            second: &quote,
+        };
+        println!("The longer part is {}", excerpt.longest_part());
+    }
 }
 
 // the borrow checker does not know how the lifetime of x and y relate to the return type
@@ -78,6 +96,62 @@ impl<'a> ImportantExcerpt<'a> {
     }
 }
 
+// a struct can hold references with different lifetimes too
+// first and second dont have to come from the same place or live as long as each other
+struct DoubleExcerpt<'a, 'b> {
+    first: &'a str,
+    second: &'b str,
+}
+
+impl<'a, 'b> DoubleExcerpt<'a, 'b> {
+    // we could return either field so the result cant promise 'a or 'b on its own
+    // the third rule gives it the lifetime of &self, and a &self can never outlive 'a or 'b
+    // (the struct would be holding a dangling reference otherwise) so that's bounded by the shorter one
+    fn longest_part(&self) -> &str {
+        if self.first.len() >= self.second.len() {
+            self.first
+        } else {
+            self.second
+        }
+    }
+}
+
+// a struct can also hand out references with the same lifetime as the one it holds
+// Parser borrows the source text and walks through it with a cursor
+struct Parser<'a> {
+    source: &'a str,
+    // byte offset of where the next search starts
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(source: &'a str) -> Parser<'a> {
+        Parser {
+            source,
+            position: 0,
+        }
+    }
+
+    // the return type says 'a and not the elided lifetime of &mut self,
+    // without it the third rule would tie each word to this borrow of the parser
+    // and we couldnt keep a word around while asking for the next one
+    fn next_word(&mut self) -> Option<&'a str> {
+        let rest = &self.source[self.position..];
+
+        // skip any whitespace in front of the word
+        let start = rest.find(|c: char| !c.is_whitespace())?;
+        let word_and_after = &rest[start..];
+
+        // the word runs until the next whitespace or the end of the source
+        let len = word_and_after
+            .find(char::is_whitespace)
+            .unwrap_or(word_and_after.len());
+
+        self.position += start + len;
+        Some(&word_and_after[..len])
+    }
+}
+
 // 'static denotes that a lifetime can live for the entirety of a program
 const stat: &'static str = "I have a static lifetime.";
 
@@ -137,3 +211,117 @@ where
     println!("Announcement! {ann}");
     if x.len() > y.len() { x } else { y }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_a_sentence_word_by_word() {
+        let mut parser = Parser::new("Call me Ishmael.");
+
+        assert_eq!(parser.next_word(), Some("Call"));
+        assert_eq!(parser.next_word(), Some("me"));
+        assert_eq!(parser.next_word(), Some("Ishmael."));
+        assert_eq!(parser.next_word(), None);
+        // stays finished
+        assert_eq!(parser.next_word(), None);
+    }
+
+    #[test]
+    fn skips_extra_whitespace() {
+        let mut parser = Parser::new("  some\tyears \n ago  ");
+
+        assert_eq!(parser.next_word(), Some("some"));
+        assert_eq!(parser.next_word(), Some("years"));
+        assert_eq!(parser.next_word(), Some("ago"));
+        assert_eq!(parser.next_word(), None);
+    }
+
+    #[test]
+    fn empty_and_blank_sources() {
+        assert_eq!(Parser::new("").next_word(), None);
+        assert_eq!(Parser::new("   ").next_word(), None);
+    }
+
+    #[test]
+    fn words_outlive_the_parser() {
+        let novel = String::from("Call me Ishmael. Some years ago...");
+
+        let words: Vec<&str>;
+        {
+            let mut parser = Parser::new(&novel);
+            let first = parser.next_word().unwrap();
+            // holding onto first while borrowing the parser mutably again is fine
+            let second = parser.next_word().unwrap();
+            words = vec![first, second];
+        }
+
+        // parser is gone but the slices point into novel which is still alive
+        assert_eq!(words, vec!["Call", "me"]);
+        assert!(std::ptr::eq(words[0].as_ptr(), novel.as_ptr()));
+    }
+
+    #[test]
+    fn works_with_multibyte_text() {
+        let mut parser = Parser::new("héllo wörld");
+
+        assert_eq!(parser.next_word(), Some("héllo"));
+        assert_eq!(parser.next_word(), Some("wörld"));
+    }
+
+    #[test]
+    fn longest_part_picks_the_longer() {
+        let excerpt = DoubleExcerpt {
+            first: "short",
+            second: "a bit longer",
+        };
+
+        assert_eq!(excerpt.longest_part(), "a bit longer");
+    }
+
+    #[test]
+    fn longest_part_prefers_first_on_a_tie() {
+        let excerpt = DoubleExcerpt {
+            first: "abc",
+            second: "xyz",
+        };
+
+        assert_eq!(excerpt.longest_part(), "abc");
+    }
+
+    #[test]
+    fn fields_can_come_from_different_scopes() {
+        let outer = String::from("lives for the whole test");
+        let first_kept;
+        {
+            let inner = String::from("inner");
+            let excerpt = DoubleExcerpt {
+                first: &outer,
+                second: &inner,
+            };
+
+            // longest_part can only be used while inner is still around
+            assert_eq!(excerpt.longest_part(), "lives for the whole test");
+
+            // but reading the field directly still gives the full 'a lifetime of outer
+            first_kept = excerpt.first;
+        }
+
+        assert_eq!(first_kept, "lives for the whole test");
+    }
+
+    #[test]
+    fn shorter_lived_part_can_be_the_longest() {
+        let outer = String::from("hi");
+        {
+            let inner = String::from("hello there");
+            let excerpt = DoubleExcerpt {
+                first: &outer,
+                second: &inner,
+            };
+
+            assert_eq!(excerpt.longest_part(), "hello there");
+        }
+    }
+}
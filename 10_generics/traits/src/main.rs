@@ -69,13 +69,16 @@ pub fn trait_bound_notify_multi_type<T: Summary + Display>(item: &T) {}
 
 // trait bound sytnax can get confusing so rust has another simplier syntax style
 // instead of the following:
-fn some_function<T: Display + Clone, U: Clone + Debug>(t: &T, u: &U) -> i32 {1}
+// fn describe_pair<T: Display + Clone, U: Clone + Debug>(t: &T, u: &U) -> String
 // you can use the following syntax:
-fn some_function_better<T, U>(t: &T, u: &U) -> i32
+// t only has to be Display and u only has to be Debug, which is why one uses {} and the other {:?}
+fn describe_pair<T, U>(t: &T, u: &U) -> String
 where
     T: Display + Clone,
     U: Clone + Debug,
-{1}
+{
+    format!("{} paired with {:?}", t, u)
+}
 
 // We can also use the impl Trait syntax in the return position to return a value of some type that implements a trait.
 // By using impl Summary for the return type, we specify that the returns_summarizable function returns some type that implements the Summary trait 
@@ -100,6 +103,24 @@ impl<T> Pair<T> {
     fn new(x: T, y: T) -> Self {
         Self { x, y }
     }
+
+    // swapping doesnt need any bounds since we never look at the values
+    fn swap(&mut self) {
+        std::mem::swap(&mut self.x, &mut self.y);
+    }
+}
+
+// larger and smaller only need to compare so PartialOrd is enough, no Display needed
+// they hand back a reference so T doesnt have to be Copy or Clone
+impl<T: PartialOrd> Pair<T> {
+    fn larger(&self) -> &T {
+        // ties go to x, same as cmp_display
+        if self.x >= self.y { &self.x } else { &self.y }
+    }
+
+    fn smaller(&self) -> &T {
+        if self.x <= self.y { &self.x } else { &self.y }
+    }
 }
 
 // here we only implement the method cmp_display if the type also implements traits Display and PartialOrd
@@ -122,6 +143,18 @@ impl<T: Display + PartialOrd> Pair<T> {
 // Because the standard library has this blanket implementation, 
 // we can call the to_string method defined by the ToString trait on any type that implements the Display trait.
 
+// here is one of our own, SummaryString is implemented for every type that implements Summary
+// so NewsArticle and SocialPost both get summary_string without us writing an impl for either
+pub trait SummaryString {
+    fn summary_string(&self) -> String;
+}
+
+impl<T: Summary> SummaryString for T {
+    fn summary_string(&self) -> String {
+        format!("[Summary] {}", self.summarize())
+    }
+}
+
 pub fn main() {
     // now users can use the trait method on types that implement it
     let post = SocialPost {
@@ -161,4 +194,119 @@ pub fn main() {
 
     println!("1 new social post: {}", post.summarize());
 
-}
\ No newline at end of file
+    // the blanket impl means summary_string is just there on both types
+    println!("{}", article.summary_string());
+    println!("{}", post.summary_string());
+
+    // where clause version, a Vec has no Display so it has to go in the Debug slot
+    println!("{}", describe_pair(&7, &vec![1, 2, 3]));
+
+    // Pair works with anything that can be compared
+    let mut pair = Pair::new(3, 8);
+    pair.cmp_display();
+    println!("larger = {}, smaller = {}", pair.larger(), pair.smaller());
+    pair.swap();
+    println!("after swap x = {}, y = {}", pair.x, pair.y);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pair_of_ints() {
+        let pair = Pair::new(3, 8);
+
+        assert_eq!(*pair.larger(), 8);
+        assert_eq!(*pair.smaller(), 3);
+    }
+
+    #[test]
+    fn pair_of_strs_compares_alphabetically() {
+        let pair = Pair::new("pear", "apple");
+
+        assert_eq!(*pair.larger(), "pear");
+        assert_eq!(*pair.smaller(), "apple");
+    }
+
+    #[test]
+    fn equal_pair() {
+        let pair = Pair::new(5, 5);
+
+        assert_eq!(*pair.larger(), 5);
+        assert_eq!(*pair.smaller(), 5);
+    }
+
+    #[test]
+    fn swap_exchanges_but_keeps_order_answers() {
+        let mut pair = Pair::new(1, 2);
+
+        pair.swap();
+
+        assert_eq!((pair.x, pair.y), (2, 1));
+        assert_eq!(*pair.larger(), 2);
+        assert_eq!(*pair.smaller(), 1);
+    }
+
+    #[test]
+    fn works_with_non_copy_types() {
+        let pair = Pair::new(String::from("b"), String::from("a"));
+
+        assert_eq!(pair.larger(), "b");
+        assert!(std::ptr::eq(pair.smaller(), &pair.y));
+    }
+
+    #[test]
+    fn summary_string_on_news_article() {
+        let article = NewsArticle {
+            headline: String::from("Penguins win the Stanley Cup Championship!"),
+            location: String::from("Pittsburgh, PA, USA"),
+            author: String::from("Iceburgh"),
+            content: String::from(
+                "The Pittsburgh Penguins once again are the best \
+                 hockey team in the NHL.",
+            ),
+        };
+
+        // uses the default summarize, which goes through summarize_author
+        assert_eq!(article.summary_string(), "[Summary] (Read more from @Iceburgh...)");
+    }
+
+    #[test]
+    fn summary_string_on_social_post() {
+        let post = SocialPost {
+            username: String::from("horse_ebooks"),
+            content: String::from("of course, as you probably already know, people"),
+            reply: false,
+            repost: false,
+        };
+
+        assert_eq!(
+            post.summary_string(),
+            "[Summary] horse_ebooks: of course, as you probably already know, people"
+        );
+    }
+
+    #[test]
+    fn summary_string_through_impl_summary() {
+        // works on the opaque impl Summary type too, since the blanket impl covers any T: Summary
+        let item = returns_summarizable();
+
+        assert!(item.summary_string().starts_with("[Summary] horse_ebooks: "));
+    }
+
+    #[test]
+    fn describe_pair_int_and_vec() {
+        assert_eq!(describe_pair(&7, &vec![1, 2, 3]), "7 paired with [1, 2, 3]");
+    }
+
+    #[test]
+    fn describe_pair_uses_display_then_debug() {
+        // Display of a String has no quotes, Debug of a &str does
+        assert_eq!(
+            describe_pair(&String::from("left"), &"right"),
+            "left paired with \"right\""
+        );
+        assert_eq!(describe_pair(&-1, &Vec::<i32>::new()), "-1 paired with []");
+    }
+}
@@ -12,6 +12,26 @@ impl Rectangle {
     fn can_hold(&self, other: &Rectangle) -> bool {
         self.width > other.width && self.height > other.height
     }
+
+    fn area(&self) -> u32 {
+        self.width * self.height
+    }
+
+    fn perimeter(&self) -> u32 {
+        2 * (self.width + self.height)
+    }
+
+    fn is_square(&self) -> bool {
+        self.width == self.height
+    }
+
+    // returns a new rectangle with both sides multiplied, so the shape stays the same
+    fn scale(&self, factor: u32) -> Rectangle {
+        Rectangle {
+            width: self.width * factor,
+            height: self.height * factor,
+        }
+    }
 }
 
 pub fn greeting(name: &str) -> String {
@@ -35,6 +55,18 @@ impl Guess {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
+    use std::fmt::Debug;
+
+    // like assert_eq! but gives back an Err instead of panicking
+    // that lets a Result returning test check several things in a row with ?
+    fn expect_eq<T: PartialEq + Debug>(actual: T, expected: T) -> Result<(), String> {
+        if actual == expected {
+            Ok(())
+        } else {
+            Err(format!("expected `{expected:?}` but got `{actual:?}`"))
+        }
+    }
 
     #[test]
     fn exploration() {
@@ -42,6 +74,32 @@ mod tests {
         assert_eq!(result, 4);
     }
 
+    // Property based tests: instead of picking a few examples ourselves, proptest generates
+    // lots of random inputs and checks a rule holds for every one of them.
+    // If one fails it shrinks the input down to the smallest case that still fails.
+    // The inputs are kept to half of u64::MAX so left + right can never overflow.
+    proptest! {
+        #[test]
+        fn add_is_commutative(a in 0..=u64::MAX / 2, b in 0..=u64::MAX / 2) {
+            prop_assert_eq!(add(a, b), add(b, a));
+        }
+
+        #[test]
+        fn add_zero_is_identity(a in any::<u64>()) {
+            prop_assert_eq!(add(a, 0), a);
+            prop_assert_eq!(add(0, a), a);
+        }
+
+        #[test]
+        fn add_is_associative(
+            a in 0..=u64::MAX / 3,
+            b in 0..=u64::MAX / 3,
+            c in 0..=u64::MAX / 3,
+        ) {
+            prop_assert_eq!(add(add(a, b), c), add(a, add(b, c)));
+        }
+    }
+
     #[test]
     fn another() {
         panic!("Make this test fail");
@@ -75,6 +133,91 @@ mod tests {
         assert!(!smaller.can_hold(&larger));
     }
 
+    #[test]
+    fn area_is_width_times_height() {
+        let rect = Rectangle {
+            width: 8,
+            height: 7,
+        };
+
+        assert_eq!(rect.area(), 56);
+    }
+
+    #[test]
+    fn perimeter_adds_all_sides() {
+        let rect = Rectangle {
+            width: 8,
+            height: 7,
+        };
+
+        assert_eq!(rect.perimeter(), 30);
+    }
+
+    #[test]
+    fn square_is_square() {
+        let square = Rectangle {
+            width: 4,
+            height: 4,
+        };
+        let not_square = Rectangle {
+            width: 4,
+            height: 5,
+        };
+
+        assert!(square.is_square());
+        assert!(!not_square.is_square());
+    }
+
+    #[test]
+    fn scale_multiplies_sides() {
+        let rect = Rectangle {
+            width: 3,
+            height: 2,
+        };
+
+        let scaled = rect.scale(3);
+
+        assert_eq!(scaled.width, 9);
+        assert_eq!(scaled.height, 6);
+        // scaling by n multiplies the area by n squared
+        assert_eq!(scaled.area(), rect.area() * 9);
+    }
+
+    #[test]
+    fn scale_preserves_aspect_ratio() {
+        let rect = Rectangle {
+            width: 8,
+            height: 6,
+        };
+
+        for factor in 1..10 {
+            let scaled = rect.scale(factor);
+            // cross multiplying avoids dividing integers
+            assert_eq!(scaled.width * rect.height, scaled.height * rect.width);
+        }
+
+        let square = Rectangle {
+            width: 5,
+            height: 5,
+        };
+        assert!(square.scale(7).is_square());
+    }
+
+    #[test]
+    fn scaled_can_hold_original() {
+        let rect = Rectangle {
+            width: 5,
+            height: 1,
+        };
+
+        let scaled = rect.scale(2);
+
+        assert!(scaled.can_hold(&rect));
+        assert!(!rect.can_hold(&scaled));
+        // scaling by 1 gives the same size, and can_hold needs to be strictly bigger
+        assert!(!rect.scale(1).can_hold(&rect));
+    }
+
     #[test]
     fn greeting_contains_name() {
         let result = greeting("Carol");
@@ -104,4 +247,43 @@ mod tests {
             Err(String::from("two plus two does not equal four"))
         }
     }
+
+    // with expect_eq each check is one line and the first mismatch ends the test
+    #[test]
+    fn add_results_in_sequence() -> Result<(), String> {
+        expect_eq(add(2, 2), 4)?;
+        expect_eq(add(0, 0), 0)?;
+        expect_eq(add(10, 5), 15)?;
+        expect_eq(add(u64::MAX - 1, 1), u64::MAX)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn expect_eq_describes_mismatch() {
+        assert_eq!(
+            expect_eq(add(2, 2), 5),
+            Err(String::from("expected `5` but got `4`"))
+        );
+    }
+
+    #[test]
+    fn expect_eq_short_circuits_on_first_failure() {
+        let mut checked = 0;
+
+        let mut check = |actual: u64, expected: u64| {
+            checked += 1;
+            expect_eq(actual, expected)
+        };
+
+        let result = (|| -> Result<(), String> {
+            check(add(1, 1), 2)?;
+            check(add(2, 2), 5)?; // wrong on purpose, the ? returns here
+            check(add(3, 3), 6)?;
+            Ok(())
+        })();
+
+        assert_eq!(result, Err(String::from("expected `5` but got `4`")));
+        assert_eq!(checked, 2);
+    }
 }
@@ -27,7 +27,7 @@ fn internal_adder(left: usize, right: usize) -> usize {
 }
 
 #[cfg(test)]
-mod tests {
+mod internal_tests {
     use super::*;
 
     #[test]
@@ -1,3 +1,49 @@
-pub fn setup() {
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// tests run in parallel so each context needs its own directory
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+// Everything a test needs, handed out by setup()
+// When the test finishes the context goes out of scope and Drop cleans up after it,
+// even if the test panicked part way through
+pub struct TestContext {
+    pub dir: PathBuf,
+}
+
+impl TestContext {
+    // a path inside this test's temp directory
+    pub fn path(&self, name: &str) -> PathBuf {
+        self.dir.join(name)
+    }
+
+    pub fn write_file(&self, name: &str, contents: &str) -> PathBuf {
+        let path = self.path(name);
+        fs::write(&path, contents).expect("failed to write test file");
+        path
+    }
+
+    pub fn read_file(&self, name: &str) -> String {
+        fs::read_to_string(self.path(name)).expect("failed to read test file")
+    }
+}
+
+impl Drop for TestContext {
+    // teardown, the whole directory goes along with anything the test put in it
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
+
+pub fn setup() -> TestContext {
     // some test setup logic here...
+    let id = NEXT_ID.fetch_add(1, Ordering::SeqCst);
+    let dir = env::temp_dir().join(format!("organizing_tests_{}_{id}", process::id()));
+
+    fs::create_dir_all(&dir).expect("failed to create test directory");
+
+    TestContext { dir }
 }
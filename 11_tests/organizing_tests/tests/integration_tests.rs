@@ -1,21 +1,44 @@
 // Each file in the tests directory is a separate crate, so we need to bring our library into each test crate’s scope.
-use adder::add_two;
+use organizing_tests::add_two;
+
+mod common;
 
 // no need to annotate this with cfg as rust treats the tests directory special
 #[test]
 fn it_adds_two() {
+    let _context = common::setup();
+
     let result = add_two(2);
     assert_eq!(result, 4);
 }
 
-use adder::add_two;
+#[test]
+fn writes_and_reads_file_in_context() {
+    let context = common::setup();
+
+    let path = context.write_file("answer.txt", &add_two(40).to_string());
 
-mod common;
+    assert!(path.starts_with(&context.dir));
+    assert_eq!(context.read_file("answer.txt"), "42");
+}
 
 #[test]
-fn it_adds_two() {
-    common::setup();
+fn context_is_cleaned_up_on_drop() {
+    let context = common::setup();
+    context.write_file("temp.txt", "short lived");
+    let dir = context.dir.clone();
+    assert!(dir.exists());
 
-    let result = add_two(2);
-    assert_eq!(result, 4);
+    drop(context);
+
+    assert!(!dir.exists());
+}
+
+#[test]
+fn each_context_gets_its_own_dir() {
+    let first = common::setup();
+    let second = common::setup();
+
+    assert_ne!(first.dir, second.dir);
+    assert!(!second.path("answer.txt").exists());
 }
@@ -1,42 +1,115 @@
-use std::{thread, time::Duration};
+use std::{
+    cell::{Ref, RefCell},
+    collections::HashMap,
+    hash::Hash,
+    thread,
+    time::Duration,
+};
 
-#[derive(Debug, PartialEq, Copy, Clone)]
+// Ord follows the order the variants are declared in, most_stocked uses it to break ties
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Copy, Clone)]
 pub enum ShirtColor {
     Red,
     Blue,
+    Green,
 }
 
+impl ShirtColor {
+    pub const ALL: [ShirtColor; 3] = [ShirtColor::Red, ShirtColor::Blue, ShirtColor::Green];
+}
+
+// Each giveaway is recorded as (what they asked for, what they got)
+type GiveawayRecord = (Option<ShirtColor>, ShirtColor);
+
 pub struct Inventory {
-    shirts: Vec<ShirtColor>
+    shirts: Vec<ShirtColor>,
+    // giveaway only takes &self, RefCell lets us push to the log anyway (interior mutability)
+    history: RefCell<Vec<GiveawayRecord>>,
 }
 
 impl Inventory {
+    pub fn new(shirts: Vec<ShirtColor>) -> Inventory {
+        Inventory {
+            shirts,
+            history: RefCell::new(vec![]),
+        }
+    }
+
     pub fn giveaway(&self, user_preference: Option<ShirtColor>) -> ShirtColor {
-        user_preference.unwrap_or_else(|| self.most_stocked())
+        let shirt = user_preference.unwrap_or_else(|| self.most_stocked());
+
+        // borrow_mut is checked at runtime, it would panic if someone was still holding the log
+        self.history.borrow_mut().push((user_preference, shirt));
+
+        shirt
+    }
+
+    // hands back a read-only borrow of the log, it must be dropped before the next giveaway
+    pub fn giveaway_log(&self) -> Ref<'_, Vec<GiveawayRecord>> {
+        self.history.borrow()
     }
 
+    // Tallies every color in a HashMap so adding a new color doesnt need a new counter
+    // On a tie the color declared later in ShirtColor wins (so Red vs Blue still gives Blue)
     pub fn most_stocked(&self) -> ShirtColor {
-        let mut red_count = 0;
-        let mut blue_count = 0;
+        // every color starts at 0 so an empty inventory still has an answer
+        let mut counts: HashMap<ShirtColor, usize> =
+            ShirtColor::ALL.iter().map(|&color| (color, 0)).collect();
+
         for color in &self.shirts {
-            match color {
-                ShirtColor::Red => red_count += 1,
-                ShirtColor::Blue => blue_count += 1,
-            };
-        } 
-        
-        if red_count > blue_count {
-            ShirtColor::Red
-        } else {
-            ShirtColor::Blue
+            *counts.entry(*color).or_insert(0) += 1;
+        }
+
+        counts
+            .into_iter()
+            .max_by_key(|&(color, count)| (count, color))
+            .map(|(color, _)| color)
+            .expect("counts always has every color")
+    }
+
+    pub fn restock(&mut self, color: ShirtColor, qty: usize) {
+        self.shirts.extend(std::iter::repeat_n(color, qty));
+    }
+}
+
+// Remembers the result of an expensive closure for every input it has seen
+// so the closure only ever runs once per distinct key
+// F only needs to be Fn since calling it doesnt change anything
+pub struct Cacher<F, K, V>
+where
+    F: Fn(K) -> V,
+{
+    calculation: F,
+    values: HashMap<K, V>,
+}
+
+impl<F, K, V> Cacher<F, K, V>
+where
+    F: Fn(K) -> V,
+    K: Eq + Hash + Clone,
+    V: Clone,
+{
+    pub fn new(calculation: F) -> Cacher<F, K, V> {
+        Cacher {
+            calculation,
+            values: HashMap::new(),
         }
     }
+
+    // the closure passed to or_insert_with only runs if the key is missing
+    // we clone the key since the map keeps one copy and the calculation gets the other
+    pub fn value(&mut self, key: K) -> V {
+        let calculation = &self.calculation;
+
+        self.values
+            .entry(key.clone())
+            .or_insert_with(|| calculation(key))
+            .clone()
+    }
 }
 
 pub fn main() {
-    let store = Inventory {
-        shirts: vec![ShirtColor::Blue, ShirtColor::Red, ShirtColor::Blue]
-    };
+    let mut store = Inventory::new(vec![ShirtColor::Blue, ShirtColor::Red, ShirtColor::Blue]);
 
     let user_preference1 = Some(ShirtColor::Red);
     let giveaway1 = store.giveaway(user_preference1);
@@ -48,6 +121,13 @@ pub fn main() {
 
     println!("User2 of preference {:?} is awarded the shirt color {:?}", user_preference2, giveaway2);
 
+    // a big delivery of green shirts changes what people without a preference get
+    store.restock(ShirtColor::Green, 5);
+    println!("User3 with no preference is awarded the shirt color {:?}", store.giveaway(None));
+
+    // even though giveaway only borrows store it has been keeping track of every giveaway
+    println!("Giveaways so far: {:?}", store.giveaway_log());
+
     // random closure example with type anno
         let expensive_closure = |num: u32| -> u32 {
         println!("calculating slowly...");
@@ -55,6 +135,11 @@ pub fn main() {
         num
     };
 
+    // wrapping it in a Cacher means the slow part only happens once per number
+    let mut cached = Cacher::new(expensive_closure);
+    println!("{}", cached.value(5));
+    println!("{}", cached.value(5)); // instant, no 'calculating slowly...'
+
     // going from fn to anon closure
     fn  add_one_v1   (x: u32) -> u32                      { x + 1 }
     let add_one_v2 = |x: u32| -> u32 { x + 1 };
@@ -101,20 +186,22 @@ pub fn main() {
         height: u32,
     }
 
-    let mut list = [
-        Rectangle { width: 10, height: 1 },
-        Rectangle { width: 3, height: 5 },
-        Rectangle { width: 7, height: 12 },
-    ];
-
-    let mut sort_operations = vec![];
-    let value = String::from("closure called");
-
-    list.sort_by_key(|r| {
-        sort_operations.push(value); // fails here as it tries to move value
-        r.width
-    });
-    println!("{list:#?}");
+    // this is commented out as it doesnt compile, sort_by_key calls the closure many times
+    // but the closure moves value out on the first call making it FnOnce not FnMut
+    // let mut list = [
+    //     Rectangle { width: 10, height: 1 },
+    //     Rectangle { width: 3, height: 5 },
+    //     Rectangle { width: 7, height: 12 },
+    // ];
+    //
+    // let mut sort_operations = vec![];
+    // let value = String::from("closure called");
+    //
+    // list.sort_by_key(|r| {
+    //     sort_operations.push(value); // fails here as it tries to move value
+    //     r.width
+    // });
+    // println!("{list:#?}");
 
     // instead we will use a mutable counter variable and use FnMut correctly
     let mut list = [
@@ -148,4 +235,135 @@ pub fn main() {
                 }
             }
         }
-*/
\ No newline at end of file
+*/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn most_stocked_with_three_colors() {
+        let store = Inventory::new(vec![
+            ShirtColor::Green,
+            ShirtColor::Red,
+            ShirtColor::Green,
+            ShirtColor::Blue,
+        ]);
+
+        assert_eq!(store.most_stocked(), ShirtColor::Green);
+    }
+
+    #[test]
+    fn most_stocked_tie_goes_to_later_color() {
+        let store = Inventory::new(vec![
+            ShirtColor::Red,
+            ShirtColor::Red,
+            ShirtColor::Blue,
+            ShirtColor::Blue,
+            ShirtColor::Green,
+        ]);
+
+        // Red and Blue tie, Blue is declared after Red
+        assert_eq!(store.most_stocked(), ShirtColor::Blue);
+    }
+
+    #[test]
+    fn most_stocked_three_way_tie() {
+        let store = Inventory::new(vec![ShirtColor::Blue, ShirtColor::Green, ShirtColor::Red]);
+
+        assert_eq!(store.most_stocked(), ShirtColor::Green);
+    }
+
+    #[test]
+    fn restock_changes_most_stocked() {
+        let mut store = Inventory::new(vec![ShirtColor::Red, ShirtColor::Red, ShirtColor::Blue]);
+        assert_eq!(store.most_stocked(), ShirtColor::Red);
+
+        store.restock(ShirtColor::Blue, 2);
+
+        assert_eq!(store.most_stocked(), ShirtColor::Blue);
+        assert_eq!(store.giveaway(None), ShirtColor::Blue);
+    }
+
+    #[test]
+    fn restock_zero_does_nothing() {
+        let mut store = Inventory::new(vec![ShirtColor::Red]);
+
+        store.restock(ShirtColor::Green, 0);
+
+        assert_eq!(store.most_stocked(), ShirtColor::Red);
+    }
+
+    #[test]
+    fn giveaway_log_starts_empty() {
+        let store = Inventory::new(vec![ShirtColor::Red]);
+
+        assert!(store.giveaway_log().is_empty());
+    }
+
+    #[test]
+    fn giveaway_log_records_each_giveaway() {
+        let store = Inventory::new(vec![ShirtColor::Blue, ShirtColor::Red, ShirtColor::Blue]);
+
+        store.giveaway(Some(ShirtColor::Red));
+        store.giveaway(None);
+        store.giveaway(Some(ShirtColor::Blue));
+
+        let log = store.giveaway_log();
+        assert_eq!(log.len(), 3);
+        assert_eq!(
+            *log,
+            vec![
+                (Some(ShirtColor::Red), ShirtColor::Red),
+                (None, ShirtColor::Blue),
+                (Some(ShirtColor::Blue), ShirtColor::Blue),
+            ]
+        );
+    }
+
+    #[test]
+    fn giveaway_log_can_be_read_between_giveaways() {
+        let store = Inventory::new(vec![ShirtColor::Red]);
+
+        store.giveaway(None);
+        assert_eq!(store.giveaway_log().len(), 1);
+
+        // the Ref from the line above has already been dropped so this doesnt panic
+        store.giveaway(None);
+        assert_eq!(store.giveaway_log().len(), 2);
+    }
+
+    #[test]
+    fn cacher_runs_closure_once_per_key() {
+        // Cell lets the Fn closure bump the counter without needing to be FnMut
+        let calls = Cell::new(0);
+        let mut cacher = Cacher::new(|x: u32| {
+            calls.set(calls.get() + 1);
+            x * 2
+        });
+
+        assert_eq!(cacher.value(2), 4);
+        assert_eq!(cacher.value(2), 4);
+        assert_eq!(cacher.value(3), 6);
+        assert_eq!(cacher.value(2), 4);
+        assert_eq!(cacher.value(3), 6);
+
+        assert_eq!(calls.get(), 2);
+    }
+
+    #[test]
+    fn cacher_with_string_keys() {
+        let calls = Cell::new(0);
+        let mut cacher = Cacher::new(|s: String| {
+            calls.set(calls.get() + 1);
+            s.len()
+        });
+
+        assert_eq!(cacher.value(String::from("hello")), 5);
+        assert_eq!(cacher.value(String::from("hi")), 2);
+        assert_eq!(cacher.value(String::from("hello")), 5);
+
+        assert_eq!(calls.get(), 2);
+    }
+}
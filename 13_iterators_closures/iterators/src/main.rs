@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 #[derive(PartialEq, Debug)]
 struct Shoe {
     size: u32,
@@ -11,6 +13,70 @@ fn shoes_in_size(shoes: Vec<Shoe>, shoe_size: u32) -> Vec<Shoe> {
     shoes.into_iter().filter(|s| s.size == shoe_size).collect()
 }
 
+// A more general version where the caller decides what to filter on by passing a closure.
+// shoes_in_size is really just filter_shoes_by(shoes, |s| s.size == shoe_size)
+fn filter_shoes_by<F: Fn(&Shoe) -> bool>(shoes: Vec<Shoe>, pred: F) -> Vec<Shoe> {
+    shoes.into_iter().filter(|s| pred(s)).collect()
+}
+
+// Buckets shoes by size, fold builds up the map as it consumes the iterator
+fn group_by_size(shoes: Vec<Shoe>) -> HashMap<u32, Vec<Shoe>> {
+    shoes.into_iter().fold(HashMap::new(), |mut groups, shoe| {
+        groups.entry(shoe.size).or_insert_with(Vec::new).push(shoe);
+        groups
+    })
+}
+
+// Our own iterator, it counts from 1 to 5
+// Implementing next() is all it takes, every other Iterator method (map, zip, sum...) comes for free
+struct Counter {
+    count: u32,
+}
+
+impl Counter {
+    fn new() -> Counter {
+        Counter { count: 0 }
+    }
+}
+
+impl Iterator for Counter {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.count < 5 {
+            self.count += 1;
+            Some(self.count)
+        } else {
+            None
+        }
+    }
+}
+
+// The "sum of products" example from the book:
+// zip pairs 1-5 with 2-5 giving (1, 2), (2, 3), (3, 4), (4, 5) (zip stops when either side runs out)
+// multiply each pair, keep only the products divisible by 3 (6 and 12) and add them up
+fn sum_of_products() -> u32 {
+    Counter::new()
+        .zip(Counter::new().skip(1))
+        .map(|(a, b)| a * b)
+        .filter(|x| x % 3 == 0)
+        .sum()
+}
+
+// Average of every sliding window of the data, e.g. window 3 over [1, 2, 3, 4] is [2, 3]
+// windows() does the sliding for us, we just map each window to its average.
+// windows(0) would panic, and a window bigger than the data has no windows at all,
+// so both of those give back an empty Vec
+fn moving_average(data: &[f64], window: usize) -> Vec<f64> {
+    if window == 0 || window > data.len() {
+        return Vec::new();
+    }
+
+    data.windows(window)
+        .map(|w| w.iter().sum::<f64>() / window as f64)
+        .collect()
+}
+
 fn main() {
     let v1 = vec![1,2,3];
 
@@ -51,6 +117,27 @@ fn main() {
     // A simply fix is to call .collect() which will consume the iterator and create a simple collection data type.
     let v2: Vec<i32>  = v1.iter().map(|x| x + 1).collect();
 
+    // Our Counter works with every adapter just like the standard library iterators
+    println!("Sum of products: {}", sum_of_products());
+
+    // windows() is another handy adapter, here it smooths out some noisy data
+    println!("{:?}", moving_average(&[1.0, 5.0, 2.0, 8.0, 3.0], 3));
+
+    // Passing our own closure lets us filter on anything, here any boot size 9 to 11
+    let shoes = vec![
+        Shoe { size: 10, style: String::from("boot") },
+        Shoe { size: 8, style: String::from("boot") },
+        Shoe { size: 10, style: String::from("sneaker") },
+    ];
+    let boots = filter_shoes_by(shoes, |s| s.style == "boot" && (9..=11).contains(&s.size));
+    println!("{boots:?}");
+
+    let shoes = vec![
+        Shoe { size: 10, style: String::from("boot") },
+        Shoe { size: 8, style: String::from("sandal") },
+    ];
+    println!("{:?}", group_by_size(shoes));
+
 }
 
 #[cfg(test)]
@@ -123,4 +210,120 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn calling_next_directly() {
+        let mut counter = Counter::new();
+
+        assert_eq!(counter.next(), Some(1));
+        assert_eq!(counter.next(), Some(2));
+        assert_eq!(counter.next(), Some(3));
+        assert_eq!(counter.next(), Some(4));
+        assert_eq!(counter.next(), Some(5));
+        assert_eq!(counter.next(), None);
+    }
+
+    #[test]
+    fn counter_sum() {
+        let total: u32 = Counter::new().sum();
+
+        assert_eq!(total, 15);
+    }
+
+    #[test]
+    fn using_other_iterator_trait_methods() {
+        assert_eq!(sum_of_products(), 18);
+    }
+
+    #[test]
+    fn counter_with_map_and_collect() {
+        let squares: Vec<u32> = Counter::new().map(|x| x * x).collect();
+
+        assert_eq!(squares, vec![1, 4, 9, 16, 25]);
+    }
+
+    #[test]
+    fn moving_average_window_of_3() {
+        let data = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+
+        assert_eq!(moving_average(&data, 3), vec![2.0, 3.0, 4.0, 5.0]);
+    }
+
+    #[test]
+    fn moving_average_uneven_values() {
+        let data = [1.0, 5.0, 2.0, 8.0];
+
+        assert_eq!(moving_average(&data, 2), vec![3.0, 3.5, 5.0]);
+    }
+
+    #[test]
+    fn moving_average_window_of_1_is_the_data() {
+        let data = [4.0, 2.0, 7.0];
+
+        assert_eq!(moving_average(&data, 1), data.to_vec());
+    }
+
+    #[test]
+    fn moving_average_window_equal_to_len() {
+        let data = [2.0, 4.0, 6.0];
+
+        assert_eq!(moving_average(&data, 3), vec![4.0]);
+    }
+
+    #[test]
+    fn moving_average_window_of_0_is_empty() {
+        assert!(moving_average(&[1.0, 2.0, 3.0], 0).is_empty());
+    }
+
+    #[test]
+    fn moving_average_window_larger_than_data_is_empty() {
+        assert!(moving_average(&[1.0, 2.0, 3.0], 4).is_empty());
+        assert!(moving_average(&[], 1).is_empty());
+    }
+
+    fn shoe(size: u32, style: &str) -> Shoe {
+        Shoe {
+            size,
+            style: String::from(style),
+        }
+    }
+
+    #[test]
+    fn filters_by_style() {
+        let shoes = vec![shoe(10, "sneaker"), shoe(13, "sandal"), shoe(10, "boot"), shoe(7, "sandal")];
+
+        let sandals = filter_shoes_by(shoes, |s| s.style == "sandal");
+
+        assert_eq!(sandals, vec![shoe(13, "sandal"), shoe(7, "sandal")]);
+    }
+
+    #[test]
+    fn filters_by_size_range() {
+        let shoes = vec![shoe(6, "boot"), shoe(9, "boot"), shoe(11, "sneaker"), shoe(12, "boot")];
+
+        // closures can capture from their environment, like this range
+        let range = 9..=11;
+        let in_range = filter_shoes_by(shoes, |s| range.contains(&s.size));
+
+        assert_eq!(in_range, vec![shoe(9, "boot"), shoe(11, "sneaker")]);
+    }
+
+    #[test]
+    fn groups_mixed_sizes() {
+        let shoes = vec![shoe(10, "sneaker"), shoe(13, "sandal"), shoe(10, "boot"), shoe(8, "boot")];
+
+        let groups = group_by_size(shoes);
+
+        assert_eq!(groups.len(), 3);
+        // the original order is kept inside each group
+        assert_eq!(groups[&10], vec![shoe(10, "sneaker"), shoe(10, "boot")]);
+        assert_eq!(groups[&13], vec![shoe(13, "sandal")]);
+        assert_eq!(groups[&8], vec![shoe(8, "boot")]);
+        assert!(!groups.contains_key(&9));
+    }
+
+    #[test]
+    fn groups_empty_list() {
+        assert!(group_by_size(vec![]).is_empty());
+    }
 }
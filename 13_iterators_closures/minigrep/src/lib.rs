@@ -1,3 +1,645 @@
+use std::{
+    env,
+    error::Error,
+    fmt,
+    fs::{self, File},
+    io::{self, BufRead, BufReader, IsTerminal},
+    path::Path,
+};
+
+// ANSI escape codes, bold red on and everything back off again
+const HIGHLIGHT_START: &str = "\x1b[1;31m";
+const HIGHLIGHT_END: &str = "\x1b[0m";
+
+// Every way minigrep can fail, so callers can match on what went wrong
+// instead of getting back a &'static str or an opaque Box<dyn Error>
+#[derive(Debug)]
+pub enum MinigrepError {
+    MissingQuery,
+    MissingFile,
+    FileRead { path: String, source: io::Error },
+    Io(io::Error),
+    Regex(String),
+    InvalidArgument(String),
+}
+
+impl fmt::Display for MinigrepError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MinigrepError::MissingQuery => write!(f, "Didn't get query string"),
+            MinigrepError::MissingFile => write!(f, "Didn't get file path"),
+            MinigrepError::FileRead { path, source } => {
+                write!(f, "Couldn't read {path}: {source}")
+            }
+            MinigrepError::Io(source) => write!(f, "I/O error: {source}"),
+            MinigrepError::Regex(pattern) => write!(f, "Invalid pattern: {pattern}"),
+            MinigrepError::InvalidArgument(message) => write!(f, "Invalid argument: {message}"),
+        }
+    }
+}
+
+// Display and Debug are all Error needs, but overriding source() lets callers
+// walk back to the underlying io::Error
+impl Error for MinigrepError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            MinigrepError::FileRead { source, .. } | MinigrepError::Io(source) => Some(source),
+            _ => None,
+        }
+    }
+}
+
+// This is what lets ? turn an io::Error into a MinigrepError for us
+impl From<io::Error> for MinigrepError {
+    fn from(error: io::Error) -> Self {
+        MinigrepError::Io(error)
+    }
+}
+
+// How results get printed, plain lines for people or JSON for scripts
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum OutputFormat {
+    Plain,
+    Json,
+}
+
+impl OutputFormat {
+    fn parse(value: &str) -> Result<OutputFormat, MinigrepError> {
+        match value {
+            "plain" => Ok(OutputFormat::Plain),
+            "json" => Ok(OutputFormat::Json),
+            other => Err(MinigrepError::InvalidArgument(format!(
+                "unknown format {other:?}, expected plain or json"
+            ))),
+        }
+    }
+}
+
+pub struct Config {
+    // a line matches if it contains any one of these
+    pub queries: Vec<String>,
+    pub file_path: String,
+    pub ignore_case: bool,
+    pub format: OutputFormat,
+    pub color: bool,
+    pub max: Option<usize>,
+    pub explain: bool,
+    // --include globs, a file is only searched if its name matches one of them (all files if empty)
+    pub include: Vec<String>,
+}
+
+impl Config {
+    pub fn build(args: impl Iterator<Item = String>) -> Result<Config, MinigrepError> {
+        let ignore_case_env = env::var("IGNORE_CASE").is_ok();
+
+        Config::build_with_env(args, ignore_case_env)
+    }
+
+    // The real parsing, with the IGNORE_CASE lookup passed in so tests can try
+    // every combination without changing the environment of the whole test process
+    fn build_with_env(
+        mut args: impl Iterator<Item = String>,
+        ignore_case_env: bool,
+    ) -> Result<Config, MinigrepError> {
+        args.next();
+
+        let mut builder = ConfigBuilder::new();
+        let mut ignore_case_flag = None;
+        let mut patterns_path = None;
+        let mut positional = vec![];
+
+        // Flags can show up anywhere, anything else is a positional argument:
+        // the first is the query and the second is the file
+        // (or with --file-patterns the queries come from the file and the only positional is the file)
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--format" => {
+                    let value = args.next().ok_or_else(|| {
+                        MinigrepError::InvalidArgument(String::from("--format needs a value"))
+                    })?;
+                    builder = builder.format(OutputFormat::parse(&value)?);
+                }
+                "--color" => builder = builder.color(true),
+                "-i" | "--ignore-case" => ignore_case_flag = Some(true),
+                "--explain" => builder = builder.explain(true),
+                // can be given more than once, a file matching any of the globs is searched
+                "--include" => {
+                    let value = args.next().ok_or_else(|| {
+                        MinigrepError::InvalidArgument(String::from("--include needs a value"))
+                    })?;
+                    builder = builder.include(value);
+                }
+                "--file-patterns" => {
+                    let value = args.next().ok_or_else(|| {
+                        MinigrepError::InvalidArgument(String::from(
+                            "--file-patterns needs a value",
+                        ))
+                    })?;
+                    patterns_path = Some(value);
+                }
+                "--max" | "-m" => {
+                    let value = args.next().ok_or_else(|| {
+                        MinigrepError::InvalidArgument(format!("{arg} needs a value"))
+                    })?;
+                    let limit = value.parse().map_err(|_| {
+                        MinigrepError::InvalidArgument(format!(
+                            "{arg} expects a number, got {value:?}"
+                        ))
+                    })?;
+                    builder = builder.max(limit);
+                }
+                _ => positional.push(arg),
+            }
+        }
+
+        // --file-patterns could come after the positionals so we only sort them out once every flag is seen
+        let mut positional = positional.into_iter();
+        match patterns_path {
+            Some(path) => builder = builder.queries(read_patterns(&path)?),
+            None => {
+                if let Some(query) = positional.next() {
+                    builder = builder.query(query);
+                }
+            }
+        }
+        if let Some(file_path) = positional.next() {
+            builder = builder.file_path(file_path);
+        }
+        if let Some(arg) = positional.next() {
+            return Err(MinigrepError::InvalidArgument(format!(
+                "unexpected argument {arg:?}"
+            )));
+        }
+
+        // the flag takes precedence, the environment variable is only a fallback
+        builder
+            .ignore_case(ignore_case_flag.unwrap_or(ignore_case_env))
+            .build()
+    }
+
+    // Whether the --include globs let this file be searched, only the file name is
+    // checked (like grep --include) so "*.rs" matches src/lib.rs too
+    pub fn includes(&self, path: &str) -> bool {
+        let name = Path::new(path)
+            .file_name()
+            .map_or(path.into(), |name| name.to_string_lossy());
+
+        self.include.is_empty() || self.include.iter().any(|glob| glob_matches(glob, &name))
+    }
+
+    // A readable summary of everything that was parsed, handy for checking flags did what you meant
+    pub fn describe(&self) -> String {
+        let case = if self.ignore_case {
+            "insensitive"
+        } else {
+            "sensitive"
+        };
+        let format = match self.format {
+            OutputFormat::Plain => "plain",
+            OutputFormat::Json => "json",
+        };
+        let color = if self.color { "on" } else { "off" };
+        let max = match self.max {
+            Some(max) => max.to_string(),
+            None => String::from("none"),
+        };
+        let include = if self.include.is_empty() {
+            String::from("all files")
+        } else {
+            self.include.join(", ")
+        };
+
+        // a single query reads the same as it always did
+        let queries = match self.queries.as_slice() {
+            [query] => format!("query: {query:?}"),
+            queries => format!("queries: {queries:?}"),
+        };
+
+        format!(
+            "{queries}\n\
+             file: {}\n\
+             case: {case}\n\
+             format: {format}\n\
+             color: {color}\n\
+             max matches: {max}\n\
+             include: {include}",
+            self.file_path
+        )
+    }
+}
+
+// Builds a Config without going through command line arguments, for tests and other code using minigrep
+// Every setter takes the builder by value and hands it back so calls can be chained:
+//     ConfigBuilder::new().query("to").file_path("poem.txt").ignore_case(true).build()
+// query sets a single pattern, queries sets several at once (a line matching any of them is a match)
+// Anything not set gets the same default the command line would give it
+#[derive(Debug, Default)]
+pub struct ConfigBuilder {
+    queries: Option<Vec<String>>,
+    file_path: Option<String>,
+    ignore_case: bool,
+    format: Option<OutputFormat>,
+    color: bool,
+    max: Option<usize>,
+    explain: bool,
+    include: Vec<String>,
+}
+
+impl ConfigBuilder {
+    pub fn new() -> ConfigBuilder {
+        ConfigBuilder::default()
+    }
+
+    pub fn query(mut self, query: impl Into<String>) -> ConfigBuilder {
+        self.queries = Some(vec![query.into()]);
+        self
+    }
+
+    pub fn queries<I, Q>(mut self, queries: I) -> ConfigBuilder
+    where
+        I: IntoIterator<Item = Q>,
+        Q: Into<String>,
+    {
+        self.queries = Some(queries.into_iter().map(Into::into).collect());
+        self
+    }
+
+    pub fn file_path(mut self, file_path: impl Into<String>) -> ConfigBuilder {
+        self.file_path = Some(file_path.into());
+        self
+    }
+
+    pub fn ignore_case(mut self, ignore_case: bool) -> ConfigBuilder {
+        self.ignore_case = ignore_case;
+        self
+    }
+
+    pub fn format(mut self, format: OutputFormat) -> ConfigBuilder {
+        self.format = Some(format);
+        self
+    }
+
+    pub fn color(mut self, color: bool) -> ConfigBuilder {
+        self.color = color;
+        self
+    }
+
+    pub fn max(mut self, max: usize) -> ConfigBuilder {
+        self.max = Some(max);
+        self
+    }
+
+    pub fn explain(mut self, explain: bool) -> ConfigBuilder {
+        self.explain = explain;
+        self
+    }
+
+    // adds to the globs rather than replacing them, same as passing --include again
+    pub fn include(mut self, glob: impl Into<String>) -> ConfigBuilder {
+        self.include.push(glob.into());
+        self
+    }
+
+    // the queries and file_path have no sensible default so leaving either out is an error
+    // (an empty list of queries is allowed though, like an empty grep -f file it just matches nothing)
+    pub fn build(self) -> Result<Config, MinigrepError> {
+        Ok(Config {
+            queries: self.queries.ok_or(MinigrepError::MissingQuery)?,
+            file_path: self.file_path.ok_or(MinigrepError::MissingFile)?,
+            ignore_case: self.ignore_case,
+            format: self.format.unwrap_or(OutputFormat::Plain),
+            color: self.color,
+            max: self.max,
+            explain: self.explain,
+            include: self.include,
+        })
+    }
+}
+
+// One query per line of the file, like grep -f. Blank lines are skipped, an empty
+// query would otherwise match every line which is almost never what a stray newline meant
+fn read_patterns(path: &str) -> Result<Vec<String>, MinigrepError> {
+    let contents = fs::read_to_string(path).map_err(|source| MinigrepError::FileRead {
+        path: path.to_string(),
+        source,
+    })?;
+
+    Ok(contents
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(String::from)
+        .collect())
+}
+
+pub fn run(config: Config) -> Result<(), MinigrepError> {
+    // minigrep only searches the one file for now, so a file the --include globs leave out
+    // means there is nothing to search. We say so instead of quietly printing no results,
+    // which would look just like a search that found nothing
+    if !config.includes(&config.file_path) {
+        return Err(MinigrepError::InvalidArgument(format!(
+            "{} is skipped by --include {}",
+            config.file_path,
+            config.include.join(", ")
+        )));
+    }
+
+    // We map the error ourselves here rather than using plain ? so the message
+    // can say which file we failed to read. The file is read a line at a time,
+    // so a read can also fail part way through and gets the same treatment
+    let file_read = |source| MinigrepError::FileRead {
+        path: config.file_path.clone(),
+        source,
+    };
+    let file = File::open(&config.file_path).map_err(file_read)?;
+    let results = find_matches_in_reader(&config, BufReader::new(file)).map_err(file_read)?;
+
+    // Escape codes only make sense on a terminal, if we are piped into a file or
+    // another program they would just show up as junk
+    let color = config.color && io::stdout().is_terminal();
+
+    match config.format {
+        OutputFormat::Plain => {
+            println!("Results:");
+            for (_, line) in results {
+                if color {
+                    println!("{}", highlight(&line, &config.queries, config.ignore_case));
+                } else {
+                    println!("{line}");
+                }
+            }
+        }
+        OutputFormat::Json => {
+            let results: Vec<(usize, &str)> = results
+                .iter()
+                .map(|(line_number, line)| (*line_number, line.as_str()))
+                .collect();
+            println!("{}", to_json(&config.file_path, &results));
+        }
+    }
+
+    Ok(())
+}
+
+// Runs the search the config asks for, stopping after config.max matches if there is one.
+// Since search is lazy take() really does stop early, the rest of the file is never searched
+pub fn find_matches<'a>(config: &Config, contents: &'a str) -> Vec<(usize, &'a str)> {
+    let results = search(&config.queries, contents, config.ignore_case);
+
+    match config.max {
+        Some(max) => results.take(max).collect(),
+        None => results.collect(),
+    }
+}
+
+// Same as find_matches but streams the lines out of reader, so only the matches are kept
+// in memory. With a max we stop reading as soon as we have enough
+pub fn find_matches_in_reader<R: BufRead>(
+    config: &Config,
+    reader: R,
+) -> io::Result<Vec<(usize, String)>> {
+    let results = search_lines(&config.queries, reader, config.ignore_case);
+
+    match config.max {
+        Some(max) => results.take(max).collect(),
+        None => results.collect(),
+    }
+}
+
+// A tiny glob matcher, * matches any run of characters (even none) and ? matches exactly one,
+// everything else has to match itself. We walk both strings once and remember where the last *
+// was, when something stops matching we go back and let that * swallow one more character.
+// Only the last * ever needs revisiting, so this stays linear-ish instead of blowing up on
+// globs with lots of stars
+fn glob_matches(glob: &str, name: &str) -> bool {
+    let glob: Vec<char> = glob.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+
+    let (mut g, mut n) = (0, 0);
+    // position of the last * in the glob and how much of the name it had swallowed
+    let mut star: Option<(usize, usize)> = None;
+
+    while n < name.len() {
+        match glob.get(g) {
+            Some('*') => {
+                star = Some((g, n));
+                g += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                g += 1;
+                n += 1;
+            }
+            _ => match star {
+                Some((star_g, star_n)) => {
+                    g = star_g + 1;
+                    n = star_n + 1;
+                    star = Some((star_g, star_n + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+
+    // the name is used up, whatever is left of the glob has to be stars
+    glob[g..].iter().all(|&c| c == '*')
+}
+
+// Like the two functions below but takes any number of queries, a line matches if it contains
+// any of them, and it also hands back the (1 based) line number of each match.
+// It returns the iterator itself rather than collecting, so the caller decides how many to take
+pub fn search<'a, Q: AsRef<str>>(
+    queries: &[Q],
+    contents: &'a str,
+    ignore_case: bool,
+) -> impl Iterator<Item = (usize, &'a str)> {
+    let queries = prepare_queries(queries, ignore_case);
+
+    contents
+        .lines()
+        .enumerate()
+        .filter(move |(_, line)| line_matches(line, &queries, ignore_case))
+        .map(|(index, line)| (index + 1, line))
+}
+
+// The streaming version of search, it reads one line at a time from anything BufRead
+// (a BufReader around a File, stdin, a Cursor in tests) so the whole input is never in memory.
+// Each line has to be owned since the buffer is reused for the next one, and reading can
+// fail part way through so every item is an io::Result. Like search it is lazy
+pub fn search_lines<R: BufRead, Q: AsRef<str>>(
+    queries: &[Q],
+    reader: R,
+    ignore_case: bool,
+) -> impl Iterator<Item = io::Result<(usize, String)>> {
+    let queries = prepare_queries(queries, ignore_case);
+
+    reader
+        .lines()
+        .enumerate()
+        .filter(move |(_, line)| match line {
+            Ok(line) => line_matches(line, &queries, ignore_case),
+            // let errors through so the caller gets to see them
+            Err(_) => true,
+        })
+        .map(|(index, line)| line.map(|line| (index + 1, line)))
+}
+
+// lowercase the queries once up front instead of for every line
+fn prepare_queries<Q: AsRef<str>>(queries: &[Q], ignore_case: bool) -> Vec<String> {
+    queries
+        .iter()
+        .map(|query| {
+            if ignore_case {
+                query.as_ref().to_lowercase()
+            } else {
+                query.as_ref().to_string()
+            }
+        })
+        .collect()
+}
+
+// Whether line contains any of the queries, they have to have gone through prepare_queries
+// already so with ignore_case only the line still needs lowercasing
+fn line_matches(line: &str, queries: &[String], ignore_case: bool) -> bool {
+    if ignore_case {
+        let line = line.to_lowercase();
+        queries.iter().any(|query| line.contains(query.as_str()))
+    } else {
+        queries.iter().any(|query| line.contains(query.as_str()))
+    }
+}
+
+// search_lines for a single query when the line numbers arent needed
+pub fn search_reader<R: BufRead>(
+    query: &str,
+    reader: R,
+    ignore_case: bool,
+) -> io::Result<Vec<String>> {
+    search_lines(&[query], reader, ignore_case)
+        .map(|result| result.map(|(_, line)| line))
+        .collect()
+}
+
+// Wraps every occurrence of any of the queries in line with the highlight escape codes.
+// Where two queries match at the same spot the longer match is highlighted.
+// Everything else in the line is copied over untouched
+pub fn highlight<Q: AsRef<str>>(line: &str, queries: &[Q], case_insensitive: bool) -> String {
+    // an empty query "matches" everywhere, there is nothing useful to highlight
+    let queries: Vec<Vec<char>> = queries
+        .iter()
+        .map(AsRef::as_ref)
+        .filter(|query| !query.is_empty())
+        .map(|query| {
+            if case_insensitive {
+                query.chars().flat_map(char::to_lowercase).collect()
+            } else {
+                query.chars().collect()
+            }
+        })
+        .collect();
+
+    if queries.is_empty() {
+        return line.to_string();
+    }
+
+    let mut highlighted = String::with_capacity(line.len());
+    let mut rest = line;
+
+    while let Some(c) = rest.chars().next() {
+        let longest = queries
+            .iter()
+            .filter_map(|query| match_len(rest, query, case_insensitive))
+            .max();
+
+        match longest {
+            Some(len) => {
+                highlighted.push_str(HIGHLIGHT_START);
+                highlighted.push_str(&rest[..len]);
+                highlighted.push_str(HIGHLIGHT_END);
+                rest = &rest[len..];
+            }
+            None => {
+                highlighted.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+
+    highlighted
+}
+
+// If text starts with query, how many bytes of text the match covers.
+// Comparing char by char (rather than lowercasing the whole line first) matters because
+// lowercasing can change how many bytes a char takes, which would throw our slicing off
+fn match_len(text: &str, query: &[char], case_insensitive: bool) -> Option<usize> {
+    let mut matched = 0;
+
+    for (index, c) in text.char_indices() {
+        if matched == query.len() {
+            return Some(index);
+        }
+
+        if case_insensitive {
+            for lower in c.to_lowercase() {
+                if query.get(matched) != Some(&lower) {
+                    return None;
+                }
+                matched += 1;
+            }
+        } else {
+            if query[matched] != c {
+                return None;
+            }
+            matched += 1;
+        }
+    }
+
+    if matched == query.len() {
+        Some(text.len())
+    } else {
+        None
+    }
+}
+
+// Builds a JSON array with one {"file", "line", "text"} object per match.
+// Writing it by hand keeps minigrep dependency free, we just have to escape strings properly
+pub fn to_json(file: &str, results: &[(usize, &str)]) -> String {
+    let entries: Vec<String> = results
+        .iter()
+        .map(|(line_number, text)| {
+            format!(
+                "{{\"file\":{},\"line\":{line_number},\"text\":{}}}",
+                json_string(file),
+                json_string(text)
+            )
+        })
+        .collect();
+
+    format!("[{}]", entries.join(","))
+}
+
+// Wraps s in quotes, escaping anything JSON doesnt allow inside a string
+fn json_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len() + 2);
+    escaped.push('"');
+
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            // any other control character has to be written as a unicode escape
+            c if (c as u32) < 0x20 => {
+                escaped.push_str(&format!("\\u{:04x}", c as u32));
+            }
+            c => escaped.push(c),
+        }
+    }
+
+    escaped.push('"');
+    escaped
+}
+
 pub fn search_case_sensitive<'a>(query: &str, contents: &'a str) -> Vec<&'a str> {
     contents
         .lines()
@@ -16,6 +658,736 @@ pub fn search_case_insensitive<'a>(query: &str, contents: &'a str) -> Vec<&'a st
 mod tests {
     use super::*;
 
+    fn args(args: &[&str]) -> impl Iterator<Item = String> {
+        args.iter().map(|arg| arg.to_string())
+    }
+
+    // parses like Config::build but with IGNORE_CASE unset, so the developer's shell cant change the results
+    fn build(arguments: &[&str]) -> Result<Config, MinigrepError> {
+        Config::build_with_env(args(arguments), false)
+    }
+
+    #[test]
+    fn build_missing_query() {
+        let err = Config::build(args(&["minigrep"])).err().unwrap();
+
+        assert!(matches!(err, MinigrepError::MissingQuery));
+        assert_eq!(err.to_string(), "Didn't get query string");
+    }
+
+    #[test]
+    fn build_missing_file() {
+        let err = Config::build(args(&["minigrep", "to"])).err().unwrap();
+
+        assert!(matches!(err, MinigrepError::MissingFile));
+        assert_eq!(err.to_string(), "Didn't get file path");
+    }
+
+    #[test]
+    fn build_with_query_and_file() {
+        let config = Config::build(args(&["minigrep", "to", "poem.txt"])).unwrap();
+
+        assert_eq!(config.queries, vec!["to"]);
+        assert_eq!(config.file_path, "poem.txt");
+    }
+
+    #[test]
+    fn file_read_error_display() {
+        let err = MinigrepError::FileRead {
+            path: String::from("missing.txt"),
+            source: io::Error::new(io::ErrorKind::NotFound, "not found"),
+        };
+
+        assert_eq!(err.to_string(), "Couldn't read missing.txt: not found");
+        assert_eq!(err.source().unwrap().to_string(), "not found");
+    }
+
+    #[test]
+    fn io_error_display() {
+        let err = MinigrepError::Io(io::Error::other("disk on fire"));
+
+        assert_eq!(err.to_string(), "I/O error: disk on fire");
+        assert!(err.source().is_some());
+    }
+
+    #[test]
+    fn regex_error_display() {
+        let err = MinigrepError::Regex(String::from("[a-"));
+
+        assert_eq!(err.to_string(), "Invalid pattern: [a-");
+        assert!(err.source().is_none());
+    }
+
+    #[test]
+    fn question_mark_converts_io_error() {
+        fn read_missing() -> Result<String, MinigrepError> {
+            let contents = fs::read_to_string("this/file/does/not/exist.txt")?;
+            Ok(contents)
+        }
+
+        let err = read_missing().unwrap_err();
+
+        assert!(matches!(err, MinigrepError::Io(ref e) if e.kind() == io::ErrorKind::NotFound));
+    }
+
+    #[test]
+    fn run_reports_which_file_failed() {
+        let config = build(&["minigrep", "to", "does_not_exist.txt"]).unwrap();
+
+        let err = run(config).unwrap_err();
+
+        assert!(
+            matches!(err, MinigrepError::FileRead { ref path, .. } if path == "does_not_exist.txt")
+        );
+    }
+
+    #[test]
+    fn build_defaults_to_plain() {
+        let config = build(&["minigrep", "to", "poem.txt"]).unwrap();
+
+        assert_eq!(config.format, OutputFormat::Plain);
+    }
+
+    #[test]
+    fn build_with_json_format() {
+        let config = build(&["minigrep", "--format", "json", "to", "poem.txt"]).unwrap();
+
+        assert_eq!(config.format, OutputFormat::Json);
+        assert_eq!(config.queries, vec!["to"]);
+        assert_eq!(config.file_path, "poem.txt");
+    }
+
+    #[test]
+    fn build_rejects_unknown_format() {
+        let err = build(&["minigrep", "to", "poem.txt", "--format", "xml"])
+            .err()
+            .unwrap();
+
+        assert!(matches!(err, MinigrepError::InvalidArgument(_)));
+    }
+
+    #[test]
+    fn build_rejects_format_without_value() {
+        let err = build(&["minigrep", "to", "poem.txt", "--format"])
+            .err()
+            .unwrap();
+
+        assert_eq!(err.to_string(), "Invalid argument: --format needs a value");
+    }
+
+    #[test]
+    fn build_rejects_extra_arguments() {
+        let err = build(&["minigrep", "to", "poem.txt", "extra"])
+            .err()
+            .unwrap();
+
+        assert!(matches!(err, MinigrepError::InvalidArgument(_)));
+    }
+
+    #[test]
+    fn search_gives_line_numbers() {
+        let contents = "\
+Rust:
+safe, fast, productive.
+Pick three.
+Trust me.";
+
+        let results: Vec<(usize, &str)> = search(&["rust"], contents, true).collect();
+
+        assert_eq!(results, vec![(1, "Rust:"), (4, "Trust me.")]);
+    }
+
+    #[test]
+    fn json_output_parses() {
+        let contents = "\
+Rust:
+safe, fast, productive.
+Pick three.
+Trust me.";
+        let results: Vec<(usize, &str)> = search(&["rust"], contents, true).collect();
+
+        let json = to_json("poem.txt", &results);
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        let entries = parsed.as_array().unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0]["file"], "poem.txt");
+        assert_eq!(entries[0]["line"], 1);
+        assert_eq!(entries[1]["line"], 4);
+        assert_eq!(entries[1]["text"], "Trust me.");
+    }
+
+    #[test]
+    fn json_escapes_strings() {
+        let results = vec![(3, "she said \"hi\" \\ left\tand\r\nright\u{1}")];
+
+        let json = to_json("a \"quoted\" file.txt", &results);
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(parsed[0]["file"], "a \"quoted\" file.txt");
+        assert_eq!(
+            parsed[0]["text"],
+            "she said \"hi\" \\ left\tand\r\nright\u{1}"
+        );
+    }
+
+    #[test]
+    fn json_with_no_matches_is_empty_array() {
+        assert_eq!(to_json("poem.txt", &[]), "[]");
+    }
+
+    #[test]
+    fn build_with_color() {
+        let config = build(&["minigrep", "--color", "to", "poem.txt"]).unwrap();
+        assert!(config.color);
+
+        let config = build(&["minigrep", "to", "poem.txt"]).unwrap();
+        assert!(!config.color);
+    }
+
+    #[test]
+    fn highlight_wraps_match() {
+        assert_eq!(
+            highlight("safe, fast, productive.", &["duct"], false),
+            "safe, fast, pro\x1b[1;31mduct\x1b[0mive."
+        );
+    }
+
+    #[test]
+    fn highlight_wraps_every_match() {
+        assert_eq!(
+            highlight("to be or not to be", &["be"], false),
+            "to \x1b[1;31mbe\x1b[0m or not to \x1b[1;31mbe\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn highlight_case_insensitive_keeps_original_case() {
+        assert_eq!(
+            highlight("Rust: Trust me.", &["rUsT"], true),
+            "\x1b[1;31mRust\x1b[0m: T\x1b[1;31mrust\x1b[0m me."
+        );
+    }
+
+    #[test]
+    fn highlight_case_sensitive_skips_other_case() {
+        assert_eq!(
+            highlight("Rust: Trust me.", &["rust"], false),
+            "Rust: T\x1b[1;31mrust\x1b[0m me."
+        );
+    }
+
+    #[test]
+    fn highlight_leaves_non_matching_line_untouched() {
+        let line = "Pick three.";
+
+        assert_eq!(highlight(line, &["duct"], false), line);
+        assert_eq!(highlight(line, &["DUCT"], true), line);
+    }
+
+    #[test]
+    fn highlight_empty_query_is_untouched() {
+        assert_eq!(highlight("Pick three.", &[""], false), "Pick three.");
+    }
+
+    #[test]
+    fn highlight_multibyte_text() {
+        assert_eq!(
+            highlight("Über straße ÜBER", &["über"], true),
+            "\x1b[1;31mÜber\x1b[0m straße \x1b[1;31mÜBER\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn build_with_max() {
+        let config = build(&["minigrep", "--max", "5", "to", "poem.txt"]).unwrap();
+        assert_eq!(config.max, Some(5));
+
+        let config = build(&["minigrep", "to", "poem.txt", "-m", "2"]).unwrap();
+        assert_eq!(config.max, Some(2));
+
+        let config = build(&["minigrep", "to", "poem.txt"]).unwrap();
+        assert_eq!(config.max, None);
+    }
+
+    #[test]
+    fn build_rejects_bad_max() {
+        let err = build(&["minigrep", "to", "poem.txt", "-m", "lots"])
+            .err()
+            .unwrap();
+
+        assert_eq!(
+            err.to_string(),
+            "Invalid argument: -m expects a number, got \"lots\""
+        );
+        assert!(build(&["minigrep", "to", "poem.txt", "--max"]).is_err());
+    }
+
+    #[test]
+    fn max_limits_matches() {
+        let contents = "\
+one match
+two match
+three match
+four match";
+        let mut config = build(&["minigrep", "match", "poem.txt", "--max", "2"]).unwrap();
+
+        assert_eq!(
+            find_matches(&config, contents),
+            vec![(1, "one match"), (2, "two match")]
+        );
+
+        config.max = None;
+        assert_eq!(find_matches(&config, contents).len(), 4);
+    }
+
+    #[test]
+    fn max_larger_than_matches_returns_all() {
+        let config = build(&["minigrep", "duct", "poem.txt", "-m", "10"]).unwrap();
+
+        assert_eq!(
+            find_matches(&config, "safe, fast, productive.\nDuct tape."),
+            vec![(1, "safe, fast, productive.")]
+        );
+    }
+
+    #[test]
+    fn max_of_zero_returns_nothing() {
+        let config = build(&["minigrep", "a", "poem.txt", "-m", "0"]).unwrap();
+
+        assert!(find_matches(&config, "a\na\na").is_empty());
+    }
+
+    #[test]
+    fn ignore_case_flag_only() {
+        let config =
+            Config::build_with_env(args(&["minigrep", "-i", "to", "poem.txt"]), false).unwrap();
+        assert!(config.ignore_case);
+
+        let config = Config::build_with_env(
+            args(&["minigrep", "to", "poem.txt", "--ignore-case"]),
+            false,
+        )
+        .unwrap();
+        assert!(config.ignore_case);
+    }
+
+    #[test]
+    fn ignore_case_env_only() {
+        let config = Config::build_with_env(args(&["minigrep", "to", "poem.txt"]), true).unwrap();
+
+        assert!(config.ignore_case);
+    }
+
+    #[test]
+    fn ignore_case_flag_and_env() {
+        let config =
+            Config::build_with_env(args(&["minigrep", "-i", "to", "poem.txt"]), true).unwrap();
+
+        assert!(config.ignore_case);
+    }
+
+    #[test]
+    fn ignore_case_neither() {
+        let config = Config::build_with_env(args(&["minigrep", "to", "poem.txt"]), false).unwrap();
+
+        assert!(!config.ignore_case);
+    }
+
+    #[test]
+    fn build_with_explain() {
+        let config = build(&["minigrep", "--explain", "to", "poem.txt"]).unwrap();
+
+        assert!(config.explain);
+    }
+
+    #[test]
+    fn describe_lists_every_setting() {
+        let config = Config::build_with_env(
+            args(&[
+                "minigrep", "-i", "--format", "json", "--color", "-m", "3", "to", "poem.txt",
+            ]),
+            false,
+        )
+        .unwrap();
+
+        let description = config.describe();
+
+        assert!(description.contains("query: \"to\""));
+        assert!(description.contains("file: poem.txt"));
+        assert!(description.contains("case: insensitive"));
+        assert!(description.contains("format: json"));
+        assert!(description.contains("color: on"));
+        assert!(description.contains("max matches: 3"));
+    }
+
+    #[test]
+    fn describe_defaults() {
+        let config = Config::build_with_env(args(&["minigrep", "to", "poem.txt"]), false).unwrap();
+
+        let description = config.describe();
+
+        assert!(description.contains("case: sensitive"));
+        assert!(description.contains("format: plain"));
+        assert!(description.contains("color: off"));
+        assert!(description.contains("max matches: none"));
+    }
+
+    #[test]
+    fn builder_sets_fields() {
+        let config = ConfigBuilder::new()
+            .query("to")
+            .file_path("poem.txt")
+            .ignore_case(true)
+            .build()
+            .unwrap();
+
+        assert_eq!(config.queries, vec!["to"]);
+        assert_eq!(config.file_path, "poem.txt");
+        assert!(config.ignore_case);
+    }
+
+    #[test]
+    fn builder_defaults_match_the_command_line() {
+        let built = ConfigBuilder::new()
+            .query("to")
+            .file_path("poem.txt")
+            .build()
+            .unwrap();
+        let parsed = Config::build_with_env(args(&["minigrep", "to", "poem.txt"]), false).unwrap();
+
+        assert_eq!(built.describe(), parsed.describe());
+        assert!(!built.ignore_case);
+        assert_eq!(built.format, OutputFormat::Plain);
+        assert!(!built.color);
+        assert_eq!(built.max, None);
+        assert!(!built.explain);
+    }
+
+    #[test]
+    fn builder_sets_every_option() {
+        let config = ConfigBuilder::new()
+            .query(String::from("rust"))
+            .file_path("notes.txt")
+            .format(OutputFormat::Json)
+            .color(true)
+            .max(3)
+            .explain(true)
+            .build()
+            .unwrap();
+
+        assert_eq!(config.format, OutputFormat::Json);
+        assert!(config.color);
+        assert_eq!(config.max, Some(3));
+        assert!(config.explain);
+    }
+
+    #[test]
+    fn builder_needs_query_and_file() {
+        let err = ConfigBuilder::new()
+            .file_path("poem.txt")
+            .build()
+            .err()
+            .unwrap();
+        assert!(matches!(err, MinigrepError::MissingQuery));
+
+        let err = ConfigBuilder::new().query("to").build().err().unwrap();
+        assert!(matches!(err, MinigrepError::MissingFile));
+    }
+
+    // writes a patterns file into the temp dir, each test uses its own name so they can run in parallel
+    fn patterns_file(name: &str, contents: &str) -> String {
+        let path = env::temp_dir().join(format!("minigrep_{name}_{}.txt", std::process::id()));
+        fs::write(&path, contents).unwrap();
+        path.to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn search_matches_any_query() {
+        let contents = "\
+Rust:
+safe, fast, productive.
+Pick three.
+Duct tape.
+Trust me.";
+
+        let results: Vec<(usize, &str)> =
+            search(&["fast", "Pick", "me."], contents, false).collect();
+
+        assert_eq!(
+            results,
+            vec![
+                (2, "safe, fast, productive."),
+                (3, "Pick three."),
+                (5, "Trust me.")
+            ]
+        );
+    }
+
+    #[test]
+    fn search_any_query_ignoring_case() {
+        let contents = "Rust:\nsafe, fast, productive.\nDuct tape.";
+
+        let results: Vec<(usize, &str)> = search(&["RUST", "duct"], contents, true).collect();
+
+        assert_eq!(
+            results,
+            vec![
+                (1, "Rust:"),
+                (2, "safe, fast, productive."),
+                (3, "Duct tape.")
+            ]
+        );
+    }
+
+    #[test]
+    fn search_with_no_queries_matches_nothing() {
+        let queries: [&str; 0] = [];
+
+        assert_eq!(search(&queries, "a\nb", false).count(), 0);
+    }
+
+    #[test]
+    fn highlight_every_query() {
+        assert_eq!(
+            highlight("safe, fast, productive.", &["fast", "safe"], false),
+            "\x1b[1;31msafe\x1b[0m, \x1b[1;31mfast\x1b[0m, productive."
+        );
+        // both start at the same spot, the longer one wins
+        assert_eq!(
+            highlight("productive", &["pro", "product"], false),
+            "\x1b[1;31mproduct\x1b[0mive"
+        );
+    }
+
+    #[test]
+    fn build_with_file_patterns() {
+        let path = patterns_file("build", "fast\n\nTrust\n");
+
+        let config = build(&["minigrep", "--file-patterns", &path, "poem.txt"]).unwrap();
+
+        // the blank line is skipped
+        assert_eq!(config.queries, vec!["fast", "Trust"]);
+        assert_eq!(config.file_path, "poem.txt");
+        assert!(config.describe().contains("queries: [\"fast\", \"Trust\"]"));
+
+        // the flag can come after the file too
+        let config = build(&["minigrep", "poem.txt", "--file-patterns", &path]).unwrap();
+        assert_eq!(config.file_path, "poem.txt");
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn file_patterns_leaves_no_room_for_a_query() {
+        let path = patterns_file("extra", "fast");
+
+        let err = build(&["minigrep", "--file-patterns", &path, "to", "poem.txt"])
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.to_string(),
+            "Invalid argument: unexpected argument \"poem.txt\""
+        );
+
+        let err = build(&["minigrep", "--file-patterns", &path])
+            .err()
+            .unwrap();
+        assert!(matches!(err, MinigrepError::MissingFile));
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn file_patterns_errors() {
+        let err = build(&[
+            "minigrep",
+            "--file-patterns",
+            "no_such_patterns.txt",
+            "poem.txt",
+        ])
+        .err()
+        .unwrap();
+        assert!(
+            matches!(err, MinigrepError::FileRead { ref path, .. } if path == "no_such_patterns.txt")
+        );
+
+        let err = build(&["minigrep", "poem.txt", "--file-patterns"])
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.to_string(),
+            "Invalid argument: --file-patterns needs a value"
+        );
+    }
+
+    #[test]
+    fn builder_with_several_queries() {
+        let config = ConfigBuilder::new()
+            .queries(["fast", "Pick"])
+            .file_path("poem.txt")
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            find_matches(&config, "Rust:\nsafe, fast, productive.\nPick three."),
+            vec![(2, "safe, fast, productive."), (3, "Pick three.")]
+        );
+    }
+
+    #[test]
+    fn glob_wildcards() {
+        assert!(glob_matches("*.rs", "lib.rs"));
+        assert!(glob_matches("*.rs", ".rs"));
+        assert!(!glob_matches("*.rs", "lib.rs.bak"));
+        assert!(!glob_matches("*.rs", "notes.txt"));
+        assert!(glob_matches("poem.???", "poem.txt"));
+        assert!(!glob_matches("poem.???", "poem.md"));
+        assert!(glob_matches("*test*", "my_tests.rs"));
+        assert!(glob_matches("Cargo.toml", "Cargo.toml"));
+        assert!(glob_matches("*", ""));
+        assert!(!glob_matches("?", ""));
+        assert!(glob_matches("a*b*c", "aXbYbZc"));
+    }
+
+    #[test]
+    fn glob_many_stars_does_not_blow_up() {
+        let name = "a".repeat(200);
+        assert!(!glob_matches("*a*a*a*a*a*a*a*a*b", &name));
+        assert!(glob_matches("*a*a*a*a*a*a*a*a*", &name));
+    }
+
+    #[test]
+    fn build_with_include() {
+        let config = build(&[
+            "minigrep",
+            "--include",
+            "*.rs",
+            "to",
+            "poem.txt",
+            "--include",
+            "*.md",
+        ])
+        .unwrap();
+
+        assert_eq!(config.include, vec!["*.rs", "*.md"]);
+        assert!(config.describe().contains("include: *.rs, *.md"));
+        assert!(build(&["minigrep", "to", "poem.txt", "--include"]).is_err());
+    }
+
+    #[test]
+    fn include_globs_union() {
+        let config = ConfigBuilder::new()
+            .query("to")
+            .file_path("poem.txt")
+            .include("*.rs")
+            .include("*.md")
+            .build()
+            .unwrap();
+
+        assert!(config.includes("src/lib.rs"));
+        assert!(config.includes("README.md"));
+        assert!(!config.includes("src/poem.txt"));
+    }
+
+    #[test]
+    fn no_include_searches_everything() {
+        let config = ConfigBuilder::new()
+            .query("to")
+            .file_path("poem.txt")
+            .build()
+            .unwrap();
+
+        assert!(config.includes("anything.at.all"));
+        assert!(config.describe().contains("include: all files"));
+    }
+
+    #[test]
+    fn run_rejects_files_not_included() {
+        // this file doesnt exist, so if run tried to read it we would get FileRead back
+        let config = ConfigBuilder::new()
+            .query("to")
+            .file_path("does_not_exist.txt")
+            .include("*.rs")
+            .include("*.md")
+            .build()
+            .unwrap();
+
+        let err = run(config).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Invalid argument: does_not_exist.txt is skipped by --include *.rs, *.md"
+        );
+    }
+
+    #[test]
+    fn search_reader_over_a_cursor() {
+        let reader = io::Cursor::new(b"Rust:\nsafe, fast, productive.\r\nPick three.\nDuct tape.");
+
+        let matches = search_reader("duct", reader, false).unwrap();
+
+        assert_eq!(matches, vec!["safe, fast, productive."]);
+    }
+
+    #[test]
+    fn search_reader_ignoring_case() {
+        let reader = io::Cursor::new("Rust:\nsafe, fast, productive.\nTrust me.\n");
+
+        let matches = search_reader("rUsT", reader, true).unwrap();
+
+        assert_eq!(matches, vec!["Rust:", "Trust me."]);
+    }
+
+    #[test]
+    fn search_reader_reports_bad_input() {
+        // not valid UTF-8, lines() gives an error for that line which we pass along
+        let reader = io::Cursor::new(&b"fine\n\xff\xfe\n"[..]);
+
+        let err = search_reader("fine", reader, false).unwrap_err();
+
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn search_reader_reads_a_real_file() {
+        let file = fs::File::open("src/poem.txt").unwrap();
+
+        let matches = search_reader("to", io::BufReader::new(file), false).unwrap();
+        let contents = fs::read_to_string("src/poem.txt").unwrap();
+
+        assert_eq!(matches, search_case_sensitive("to", &contents));
+    }
+
+    #[test]
+    fn search_lines_matches_search() {
+        let contents = "Rust:\nsafe, fast, productive.\nPick three.\nTrust me.";
+        let queries = ["rust", "pick"];
+
+        let streamed: Vec<(usize, String)> =
+            search_lines(&queries, io::Cursor::new(contents), true)
+                .collect::<io::Result<_>>()
+                .unwrap();
+        let in_memory: Vec<(usize, String)> = search(&queries, contents, true)
+            .map(|(number, line)| (number, line.to_string()))
+            .collect();
+
+        assert_eq!(streamed, in_memory);
+        assert_eq!(streamed.len(), 3);
+    }
+
+    #[test]
+    fn find_matches_in_reader_stops_at_max() {
+        let config = build(&["minigrep", "--max", "2", "a", "poem.txt"]).unwrap();
+        // the bad line comes after the second match so it is never read
+        let reader = io::Cursor::new(&b"a\na\n\xff\na\n"[..]);
+
+        assert_eq!(
+            find_matches_in_reader(&config, reader).unwrap(),
+            vec![(1, String::from("a")), (2, String::from("a"))]
+        );
+    }
+
     #[test]
     fn case_sensitive() {
         let query = "duct";
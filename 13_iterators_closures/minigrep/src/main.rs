@@ -1,7 +1,6 @@
-use core::arch;
-use std::{env, error::Error, fs, process};
+use std::{env, process};
 
-use minigrep::{search_case_insensitive, search_case_sensitive};
+use minigrep::{Config, OutputFormat, run};
 
 fn main() {
     let config = Config::build(env::args()).unwrap_or_else(|err| {
@@ -9,60 +8,24 @@ fn main() {
         process::exit(1);
     });
 
-    println!(
-        "Searching for {} in file: {}",
-        config.query, config.file_path
-    );
-    println!();
-
-    if let Err(e) = run(config) {
-        eprintln!("Application error: {e}");
-        process::exit(1);
+    // --explain is for checking what the arguments parsed to, so we stop before searching
+    if config.explain {
+        println!("{}", config.describe());
+        return;
     }
-}
 
-fn run(config: Config) -> Result<(), Box<dyn Error>> {
-    let contents = fs::read_to_string(config.file_path)?;
-    println!("Results:");
-    let results = if config.ignore_case {
-        search_case_insensitive(&config.query, &contents)
-    } else {
-        search_case_sensitive(&config.query, &contents)
-    };
-
-    for line in results {
-        println!("{line}");
+    // JSON output should be nothing but JSON so scripts can parse it
+    if config.format == OutputFormat::Plain {
+        println!(
+            "Searching for {} in file: {}",
+            config.queries.join(" or "),
+            config.file_path
+        );
+        println!();
     }
 
-    Ok(())
-}
-
-struct Config {
-    pub query: String,
-    pub file_path: String,
-    pub ignore_case: bool,
-}
-
-impl Config {
-    pub fn build(mut args: impl Iterator<Item = String>) -> Result<Config, &'static str> {
-        args.next();
-
-        let query = match args.next() {
-            Some(args) => args,
-            None => return Err("Didn't get query string"),
-        };
-
-        let file_path = match args.next() {
-            Some(args) => args,
-            None => return Err("Didn't get file path"),
-        };
-
-        let ignore_case = env::var("IGNORE_CASE").is_ok();
-
-        Ok(Config {
-            query,
-            file_path,
-            ignore_case,
-        })
+    if let Err(e) = run(config) {
+        eprintln!("Application error: {e}");
+        process::exit(1);
     }
 }
@@ -6,12 +6,79 @@ enum List {
 }
 */
 
-enum List {
-    Cons(i32, Box<List>),
+// Generic over the element type, the Box is still what gives List<T> a known size
+// since a Cons only stores a pointer to the rest of the list, whatever T is
+#[derive(Debug, PartialEq)]
+enum List<T> {
+    Cons(T, Box<List<T>>),
     Nil,
 }
 
+impl<T> List<T> {
+    // Builds the list back to front so each new Cons can wrap the list made so far
+    // fold is a plain loop so building doesnt recurse, but the derived Debug and PartialEq
+    // and the default drop still walk the list recursively, so a very long list can still overflow
+    fn from_vec(v: Vec<T>) -> List<T> {
+        v.into_iter()
+            .rev()
+            .fold(Nil, |rest, value| Cons(value, Box::new(rest)))
+    }
+
+    fn iter(&self) -> Iter<'_, T> {
+        Iter { current: self }
+    }
+
+    // walks the list one Box at a time until it hits Nil
+    fn len(&self) -> usize {
+        self.iter().count()
+    }
+}
+
+// Borrows the list and hands out a reference to each value from front to back
+struct Iter<'a, T> {
+    current: &'a List<T>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        match self.current {
+            Cons(value, rest) => {
+                self.current = rest;
+                Some(value)
+            }
+            Nil => None,
+        }
+    }
+}
+
+// With From implemented we also get Into for free, so vec.into() works where a List is expected
+impl<T> From<Vec<T>> for List<T> {
+    fn from(v: Vec<T>) -> List<T> {
+        List::from_vec(v)
+    }
+}
+
+// Going back the other way takes the list by value so each Box can be moved out of
+impl<T> From<List<T>> for Vec<T> {
+    fn from(list: List<T>) -> Vec<T> {
+        let mut values = Vec::with_capacity(list.len());
+        let mut current = list;
+
+        while let Cons(value, rest) = current {
+            values.push(value);
+            current = *rest;
+        }
+
+        values
+    }
+}
+
 use crate::List::{Cons, Nil};
+use crate::node::{BinaryOp, Literal, Node, Op};
+
+mod node;
 
 fn main() {
     // An example of creating a box for an i32 value
@@ -23,4 +90,104 @@ fn main() {
 
     // Proper definition of a cons list using a Box indirection
     let list = Cons(1, Box::new(Cons(2, Box::new(Cons(3, Box::new(Nil))))));
-}
\ No newline at end of file
+
+    // or let from_vec do all the boxing for us
+    let built = List::from_vec(vec![1, 2, 3]);
+    println!(
+        "built has {} items, same as list: {}",
+        built.len(),
+        built == list
+    );
+
+    // List is generic so it can hold more than i32s
+    let words = List::from_vec(vec![String::from("hello"), String::from("world")]);
+    for word in words.iter() {
+        println!("{word}");
+    }
+
+    // and From/Into turn it back into a Vec
+    let back: Vec<i32> = built.into();
+    println!("back to a vec = {back:?}");
+
+    // Box also lets us hold a trait object, here a tree for (1 + 2) * (7 - 3)
+    let sum = BinaryOp::new(Op::Add, Box::new(Literal(1)), Box::new(Literal(2)));
+    let difference = BinaryOp::new(Op::Sub, Box::new(Literal(7)), Box::new(Literal(3)));
+    let tree = BinaryOp::new(Op::Mul, Box::new(sum), Box::new(difference));
+    println!("tree = {}", tree.eval());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_vec_keeps_the_order() {
+        let list = List::from_vec(vec![1, 2, 3]);
+
+        assert_eq!(
+            list,
+            Cons(1, Box::new(Cons(2, Box::new(Cons(3, Box::new(Nil))))))
+        );
+        assert_eq!(list.len(), 3);
+    }
+
+    #[test]
+    fn from_empty_vec_is_nil() {
+        let list: List<i32> = List::from_vec(vec![]);
+
+        assert_eq!(list, Nil);
+        assert_eq!(list.len(), 0);
+    }
+
+    #[test]
+    fn len_of_a_longer_list() {
+        let list = List::from_vec((0..10_000).collect());
+
+        assert_eq!(list.len(), 10_000);
+    }
+
+    #[test]
+    fn round_trips_through_a_list() {
+        let list: List<i32> = vec![1, 2, 3].into();
+        assert_eq!(list.len(), 3);
+
+        let back: Vec<i32> = list.into();
+
+        assert_eq!(back, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn round_trips_empty() {
+        let list: List<i32> = List::from(Vec::new());
+        assert_eq!(list, Nil);
+
+        assert_eq!(Vec::from(list), Vec::new());
+    }
+
+    #[test]
+    fn list_of_strings() {
+        let list = List::from_vec(vec![String::from("a"), String::from("bc")]);
+
+        let lengths: Vec<usize> = list.iter().map(|s| s.len()).collect();
+
+        assert_eq!(list.len(), 2);
+        assert_eq!(lengths, vec![1, 2]);
+    }
+
+    #[test]
+    fn list_of_floats() {
+        let list = Cons(1.5, Box::new(Cons(2.5, Box::new(Nil))));
+
+        assert_eq!(list.len(), 2);
+        assert_eq!(list.iter().sum::<f64>(), 4.0);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1.5, &2.5]);
+    }
+
+    #[test]
+    fn iter_on_nil_is_empty() {
+        let list: List<f64> = Nil;
+
+        assert_eq!(list.iter().next(), None);
+        assert_eq!(list.len(), 0);
+    }
+}
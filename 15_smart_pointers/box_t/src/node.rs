@@ -0,0 +1,99 @@
+// Anything in the expression tree, a number or an operation on two other nodes
+// Children are stored as Box<dyn Node> so one tree can mix different node types,
+// the Box gives every child the same known size and eval is looked up at runtime
+pub trait Node {
+    fn eval(&self) -> i32;
+}
+
+pub struct Literal(pub i32);
+
+impl Node for Literal {
+    fn eval(&self) -> i32 {
+        self.0
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Op {
+    Add,
+    Sub,
+    Mul,
+}
+
+pub struct BinaryOp {
+    op: Op,
+    left: Box<dyn Node>,
+    right: Box<dyn Node>,
+}
+
+impl BinaryOp {
+    // takes the children already boxed so any Node (even another BinaryOp) can go in
+    pub fn new(op: Op, left: Box<dyn Node>, right: Box<dyn Node>) -> BinaryOp {
+        BinaryOp { op, left, right }
+    }
+}
+
+impl Node for BinaryOp {
+    fn eval(&self) -> i32 {
+        let left = self.left.eval();
+        let right = self.right.eval();
+
+        match self.op {
+            Op::Add => left + right,
+            Op::Sub => left - right,
+            Op::Mul => left * right,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn literal_evaluates_to_itself() {
+        assert_eq!(Literal(7).eval(), 7);
+    }
+
+    #[test]
+    fn evaluates_a_small_tree() {
+        // (10 - 4) * (2 + 3)
+        let tree = BinaryOp::new(
+            Op::Mul,
+            Box::new(BinaryOp::new(
+                Op::Sub,
+                Box::new(Literal(10)),
+                Box::new(Literal(4)),
+            )),
+            Box::new(BinaryOp::new(
+                Op::Add,
+                Box::new(Literal(2)),
+                Box::new(Literal(3)),
+            )),
+        );
+
+        assert_eq!(tree.eval(), 30);
+    }
+
+    #[test]
+    fn mixed_nodes_in_one_vec() {
+        // the Vec only knows it holds some kind of Node
+        let nodes: Vec<Box<dyn Node>> = vec![
+            Box::new(Literal(1)),
+            Box::new(BinaryOp::new(
+                Op::Add,
+                Box::new(Literal(1)),
+                Box::new(Literal(1)),
+            )),
+            Box::new(BinaryOp::new(
+                Op::Sub,
+                Box::new(Literal(0)),
+                Box::new(Literal(3)),
+            )),
+        ];
+
+        let results: Vec<i32> = nodes.iter().map(|node| node.eval()).collect();
+
+        assert_eq!(results, vec![1, 2, -3]);
+    }
+}
@@ -1,9 +1,40 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+#[macro_use]
+mod scope_guard;
+
+/// A shared list of what was dropped, in the order it happened
+///
+type DropLog = Rc<RefCell<Vec<String>>>;
+
 /// A Custom Smart Pointer Type
 /// 
 /// Will serve to showcase the Drop Trait
 /// 
 struct CustomSmartPointer {
     data: String,
+    log: Option<DropLog>,
+}
+
+impl CustomSmartPointer {
+    /// Same as before, just prints when dropped
+    ///
+    fn new(data: &str) -> CustomSmartPointer {
+        CustomSmartPointer {
+            data: String::from(data),
+            log: None,
+        }
+    }
+
+    /// Also writes its data into log when dropped so we can check the order afterwards
+    ///
+    fn logged(data: &str, log: &DropLog) -> CustomSmartPointer {
+        CustomSmartPointer {
+            data: String::from(data),
+            log: Some(Rc::clone(log)),
+        }
+    }
 }
 
 /// Implement the Drop Trait
@@ -15,25 +46,147 @@ struct CustomSmartPointer {
 impl Drop for CustomSmartPointer {
     fn drop(&mut self) {
         println!("Dropping CustomSmartPointer with data `{}`!", self.data);
+
+        if let Some(log) = &self.log {
+            log.borrow_mut().push(self.data.clone());
+        }
+    }
+}
+
+/// Holds smart pointers of its own to show the order nested values are dropped in
+///
+/// When a Container goes out of scope its own drop runs first, then its fields are dropped
+/// in the order they are declared: every element of items (first to last), then inner
+///
+struct Container {
+    name: String,
+    items: Vec<CustomSmartPointer>,
+    inner: CustomSmartPointer,
+    log: Option<DropLog>,
+}
+
+impl Drop for Container {
+    fn drop(&mut self) {
+        println!("Dropping Container `{}`!", self.name);
+
+        if let Some(log) = &self.log {
+            log.borrow_mut().push(self.name.clone());
+        }
     }
 }
 
 fn main() {
     // Lets create these two SmartPointers
-    let c = CustomSmartPointer {
-        data: String::from("my stuff"),
-    };
+    let c = CustomSmartPointer::new("my stuff");
 
-    let d = CustomSmartPointer {
-        data: String::from("other stuff"),
-    };
+    let d = CustomSmartPointer::new("other stuff");
     println!("CustomSmartPointers created");
 
     // Lets look at an example of dropping early using `drop()`
-    let e = CustomSmartPointer {
-        data: String::from("some data"),
-    };
+    let e = CustomSmartPointer::new("some data");
     println!("CustomSmartPointer created");
     drop(e);
     println!("CustomSmartPointer dropped before the end of main");
-} // d and c will be dropped when out of scope
+
+    // Lets look at nested values, the log remembers the order everything was dropped in
+    let log: DropLog = Rc::new(RefCell::new(Vec::new()));
+    {
+        let container = Container {
+            name: String::from("box of stuff"),
+            items: vec![
+                CustomSmartPointer::logged("first item", &log),
+                CustomSmartPointer::logged("second item", &log),
+            ],
+            inner: CustomSmartPointer::logged("inner stuff", &log),
+            log: Some(Rc::clone(&log)),
+        };
+        println!(
+            "Container created with {} items and `{}` inside",
+            container.items.len(),
+            container.inner.data
+        );
+    } // the container goes first, then its fields in the order they are declared
+    println!("Drop order: {:?}", log.borrow());
+
+    // A ScopeGuard runs any closure we like when dropped, defer! makes one for the rest of the scope
+    {
+        defer! { println!("Cleaning up at the end of the scope") }
+        println!("Doing some work before the cleanup");
+    }
+} // d and c will be dropped when out of scope (reverse order of creation)
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_log() -> DropLog {
+        Rc::new(RefCell::new(Vec::new()))
+    }
+
+    #[test]
+    fn locals_drop_in_reverse_order() {
+        let log = new_log();
+
+        {
+            let _first = CustomSmartPointer::logged("first", &log);
+            let _second = CustomSmartPointer::logged("second", &log);
+        }
+
+        assert_eq!(*log.borrow(), ["second", "first"]);
+    }
+
+    #[test]
+    fn container_drops_itself_then_items_then_inner() {
+        let log = new_log();
+
+        {
+            let _before = CustomSmartPointer::logged("before", &log);
+            let _container = Container {
+                name: String::from("container"),
+                items: vec![
+                    CustomSmartPointer::logged("item 0", &log),
+                    CustomSmartPointer::logged("item 1", &log),
+                ],
+                inner: CustomSmartPointer::logged("inner", &log),
+                log: Some(Rc::clone(&log)),
+            };
+            let _after = CustomSmartPointer::logged("after", &log);
+        }
+
+        // locals go last to first, and the whole container (with its fields) is
+        // dropped in between its two siblings
+        assert_eq!(
+            *log.borrow(),
+            ["after", "container", "item 0", "item 1", "inner", "before"]
+        );
+    }
+
+    #[test]
+    fn early_drop_happens_right_away() {
+        let log = new_log();
+
+        let early = CustomSmartPointer::logged("early", &log);
+        let _late = CustomSmartPointer::logged("late", &log);
+        drop(early);
+
+        assert_eq!(*log.borrow(), ["early"]);
+    }
+
+    #[test]
+    fn drop_still_runs_when_unwinding_from_a_panic() {
+        // with the default panic = 'unwind' the stack is unwound and every value in it
+        // is dropped, with panic = 'abort' in Cargo.toml the process would just stop
+        // and this test could not run at all
+        let log = new_log();
+
+        // the log is made just for this test so nothing else sees the panic,
+        // AssertUnwindSafe is needed since RefCell could be left half changed by a panic
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let _pointer = CustomSmartPointer::logged("unwound", &log);
+            panic!("something went wrong");
+        }));
+
+        assert!(result.is_err());
+        assert_eq!(*log.borrow(), ["unwound"]);
+    }
+}
@@ -0,0 +1,85 @@
+/// Runs a closure when it goes out of scope
+///
+/// This is the RAII pattern, the cleanup is tied to a value so it happens no matter
+/// how the scope is left (falling off the end, an early return, ? or even a panic)
+///
+pub struct ScopeGuard<F: FnMut()> {
+    on_drop: F,
+}
+
+impl<F: FnMut()> ScopeGuard<F> {
+    pub fn new(on_drop: F) -> ScopeGuard<F> {
+        ScopeGuard { on_drop }
+    }
+}
+
+impl<F: FnMut()> Drop for ScopeGuard<F> {
+    fn drop(&mut self) {
+        (self.on_drop)();
+    }
+}
+
+/// Runs the given code at the end of the current scope, like defer in Go
+///
+/// The guard is bound to a hidden variable so it lives until the scope ends,
+/// binding it to `_` instead would drop it (and run the code) straight away
+///
+macro_rules! defer {
+    ($($body:tt)*) => {
+        let _guard = $crate::scope_guard::ScopeGuard::new(|| { $($body)* });
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn runs_only_when_scope_ends() {
+        let counter = Cell::new(0);
+
+        {
+            let _guard = ScopeGuard::new(|| counter.set(counter.get() + 1));
+            assert_eq!(counter.get(), 0);
+        }
+
+        assert_eq!(counter.get(), 1);
+    }
+
+    #[test]
+    fn defer_runs_on_early_return() {
+        fn work(counter: &Cell<u32>, stop_early: bool) -> &'static str {
+            let start = counter.get();
+            defer! { counter.set(counter.get() + 1) }
+
+            if stop_early {
+                return "stopped early";
+            }
+
+            // still inside the scope so the guard hasnt run yet
+            assert_eq!(counter.get(), start);
+            "finished"
+        }
+
+        let counter = Cell::new(0);
+
+        assert_eq!(work(&counter, true), "stopped early");
+        assert_eq!(counter.get(), 1);
+        assert_eq!(work(&counter, false), "finished");
+        assert_eq!(counter.get(), 2);
+    }
+
+    #[test]
+    fn several_defers_run_in_reverse_order() {
+        let order = std::cell::RefCell::new(Vec::new());
+
+        {
+            defer! { order.borrow_mut().push("first") }
+            defer! { order.borrow_mut().push("second") }
+        }
+
+        // guards are locals too so the last one made is dropped first
+        assert_eq!(*order.borrow(), ["second", "first"]);
+    }
+}
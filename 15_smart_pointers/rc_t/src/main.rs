@@ -4,13 +4,81 @@ enum List {
 }
 
 use crate::List::{Cons, Nil};
+use crate::weak_cache::WeakCache;
 use std::rc::Rc;
 
+mod weak_cache;
+
+// how many Rc's are pointing at this list right now (its owners)
+fn strong_count(list: &Rc<List>) -> usize {
+    Rc::strong_count(list)
+}
+
 fn main() {
     // Well first create a Cons list of 5,10 using Rc<T>
     let a = Rc::new(Cons(5, Rc::new(Cons(10, Rc::new(Nil)))));
+    println!("count after creating a = {}", strong_count(&a));
 
     // We created a with Rc<T> so we can have b and c below point to a without taking ownership
+    // every Rc::clone bumps the count by one instead of deep copying the list
     let b = Cons(3, Rc::clone(&a));
-    let c = Cons(4, Rc::clone(&a));
-}
\ No newline at end of file
+    println!("count after creating b = {}", strong_count(&a));
+    {
+        let c = Cons(4, Rc::clone(&a));
+        println!("count after creating c = {}", strong_count(&a));
+    } // c goes out of scope here and its clone is dropped
+    println!("count after c goes out of scope = {}", strong_count(&a));
+
+    // A WeakCache hands out Rc's but only keeps a Weak for itself, so it never keeps a value alive
+    let mut cache = WeakCache::new();
+    let greeting = cache.get_or_insert_with("greeting", || String::from("hello"));
+    let again = cache.get_or_insert_with("greeting", || String::from("never made"));
+    println!("{again}, strong count = {}", Rc::strong_count(&greeting));
+
+    drop(greeting);
+    drop(again);
+    cache.purge();
+    println!("entries left after purge = {}", cache.len());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn count_goes_up_with_each_clone() {
+        let a = Rc::new(Cons(5, Rc::new(Cons(10, Rc::new(Nil)))));
+        assert_eq!(strong_count(&a), 1);
+
+        let _b = Cons(3, Rc::clone(&a));
+        assert_eq!(strong_count(&a), 2);
+
+        let _c = Cons(4, Rc::clone(&a));
+        assert_eq!(strong_count(&a), 3);
+    }
+
+    #[test]
+    fn count_goes_down_when_a_clone_is_dropped() {
+        let a = Rc::new(Cons(5, Rc::new(Nil)));
+        let b = Cons(3, Rc::clone(&a));
+        let c = Cons(4, Rc::clone(&a));
+        assert_eq!(strong_count(&a), 3);
+
+        drop(c);
+        assert_eq!(strong_count(&a), 2);
+
+        drop(b);
+        assert_eq!(strong_count(&a), 1);
+    }
+
+    #[test]
+    fn sharing_the_head_doesnt_touch_the_tail() {
+        let tail = Rc::new(Cons(10, Rc::new(Nil)));
+        let a = Rc::new(Cons(5, Rc::clone(&tail)));
+        let _b = Cons(3, Rc::clone(&a));
+
+        // only a was cloned, the tail is still owned by tail and a
+        assert_eq!(strong_count(&a), 2);
+        assert_eq!(strong_count(&tail), 2);
+    }
+}
@@ -0,0 +1,94 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::rc::{Rc, Weak};
+
+// A cache that doesnt keep its values alive by itself
+// It only stores Weak<V>, so once every Rc<V> handed out is dropped the value is freed
+// and the entry left behind is just a dead Weak that purge can clean up
+pub struct WeakCache<K, V> {
+    entries: HashMap<K, Weak<V>>,
+}
+
+impl<K: Eq + Hash, V> WeakCache<K, V> {
+    pub fn new() -> WeakCache<K, V> {
+        WeakCache {
+            entries: HashMap::new(),
+        }
+    }
+
+    // if someone still holds the value upgrade gives us another Rc to it,
+    // otherwise (never made or already freed) we build it again with make
+    pub fn get_or_insert_with<F: FnOnce() -> V>(&mut self, key: K, make: F) -> Rc<V> {
+        if let Some(value) = self.entries.get(&key).and_then(Weak::upgrade) {
+            return value;
+        }
+
+        let value = Rc::new(make());
+        self.entries.insert(key, Rc::downgrade(&value));
+        value
+    }
+
+    // drops every entry whose value has already been freed
+    pub fn purge(&mut self) {
+        self.entries.retain(|_, value| value.strong_count() > 0);
+    }
+
+    // counts dead entries too until purge is called
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_value_while_a_strong_ref_is_held() {
+        let mut cache = WeakCache::new();
+
+        let first = cache.get_or_insert_with("a", || String::from("apple"));
+        let second = cache.get_or_insert_with("a", || String::from("not used"));
+
+        assert!(Rc::ptr_eq(&first, &second));
+        assert_eq!(*second, "apple");
+        // first, second and nothing from the cache itself
+        assert_eq!(Rc::strong_count(&first), 2);
+    }
+
+    #[test]
+    fn entry_survives_purge_while_held() {
+        let mut cache = WeakCache::new();
+        let value = cache.get_or_insert_with(1, || 10);
+
+        cache.purge();
+
+        assert_eq!(cache.len(), 1);
+        assert_eq!(*cache.get_or_insert_with(1, || 99), 10);
+        drop(value);
+    }
+
+    #[test]
+    fn entry_is_purged_after_last_strong_ref_drops() {
+        let mut cache = WeakCache::new();
+        let kept = cache.get_or_insert_with("kept", || 1);
+        let dropped = cache.get_or_insert_with("dropped", || 2);
+
+        drop(dropped);
+        // the dead entry is still there until we purge
+        assert_eq!(cache.len(), 2);
+
+        cache.purge();
+
+        assert_eq!(cache.len(), 1);
+        assert_eq!(*kept, 1);
+    }
+
+    #[test]
+    fn freed_value_is_made_again() {
+        let mut cache = WeakCache::new();
+        drop(cache.get_or_insert_with("a", || 1));
+
+        assert_eq!(*cache.get_or_insert_with("a", || 2), 2);
+    }
+}
@@ -77,9 +77,12 @@ enum List {
 }
 
 use crate::List::{Cons, Nil};
+use crate::observable::Observable;
 use std::cell::RefCell;
 use std::rc::Rc;
 
+mod observable;
+
 fn main() {
     // We create a value that is an instance of Rc<RefCell<i32>> and store it in a variable named value so that we can access it directly later. 
     let value = Rc::new(RefCell::new(5));
@@ -97,4 +100,10 @@ fn main() {
     println!("a after = {a:?}");
     println!("b after = {b:?}");
     println!("c after = {c:?}");
+
+    // An Observable mixes RefCell with closures, everyone subscribed hears about each set
+    let temperature = Observable::new(20);
+    temperature.subscribe(|t| println!("temperature is now {t}"));
+    temperature.set(25);
+    println!("temperature ended at {}", temperature.get());
 }
\ No newline at end of file
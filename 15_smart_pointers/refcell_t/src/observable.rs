@@ -0,0 +1,91 @@
+use std::cell::RefCell;
+
+// Every listener is a boxed closure so different closures can sit in the same Vec
+type Listener<T> = Box<dyn Fn(&T)>;
+
+// A value that tells everyone who subscribed whenever it changes
+// Both fields are RefCells so set and subscribe only need &self (interior mutability),
+// which means an Observable can be shared through an Rc and still be changed
+pub struct Observable<T> {
+    value: RefCell<T>,
+    listeners: RefCell<Vec<Listener<T>>>,
+}
+
+impl<T> Observable<T> {
+    pub fn new(value: T) -> Observable<T> {
+        Observable {
+            value: RefCell::new(value),
+            listeners: RefCell::new(vec![]),
+        }
+    }
+
+    pub fn subscribe<F: Fn(&T) + 'static>(&self, listener: F) {
+        self.listeners.borrow_mut().push(Box::new(listener));
+    }
+
+    // the mutable borrow of value ends before any listener runs so they can all read it,
+    // a listener calling set or subscribe on the same Observable would panic though
+    // since the listeners are still borrowed while we loop over them
+    pub fn set(&self, value: T) {
+        *self.value.borrow_mut() = value;
+
+        let value = self.value.borrow();
+        for listener in self.listeners.borrow().iter() {
+            listener(&value);
+        }
+    }
+
+    pub fn get(&self) -> T
+    where
+        T: Clone,
+    {
+        self.value.borrow().clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+
+    #[test]
+    fn listener_sees_every_set() {
+        let observable = Observable::new(0);
+        let seen = Rc::new(RefCell::new(vec![]));
+
+        let log = Rc::clone(&seen);
+        observable.subscribe(move |value: &i32| log.borrow_mut().push(*value));
+
+        observable.set(1);
+        observable.set(5);
+        observable.set(5);
+
+        assert_eq!(*seen.borrow(), vec![1, 5, 5]);
+        assert_eq!(observable.get(), 5);
+    }
+
+    #[test]
+    fn every_listener_is_called() {
+        let observable = Observable::new(String::new());
+        let seen = Rc::new(RefCell::new(vec![]));
+
+        for name in ["first", "second"] {
+            let log = Rc::clone(&seen);
+            observable
+                .subscribe(move |value: &String| log.borrow_mut().push(format!("{name}: {value}")));
+        }
+
+        observable.set(String::from("hi"));
+
+        assert_eq!(*seen.borrow(), vec!["first: hi", "second: hi"]);
+    }
+
+    #[test]
+    fn set_without_listeners_just_updates() {
+        let observable = Observable::new(1);
+
+        observable.set(2);
+
+        assert_eq!(observable.get(), 2);
+    }
+}
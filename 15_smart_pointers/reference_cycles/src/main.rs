@@ -39,6 +39,31 @@ fn main() {
     // Uncomment the next line to see that we have a cycle;
     // it will overflow the stack.
     // println!("a next item = {:?}", a.tail());
+
+    // Weak parent pointers let us build a proper tree with no cycle, see Node below
+    let root = Node::new(1);
+    let branch = Node::new(2);
+    Node::add_child(&root, Rc::clone(&branch));
+    Node::add_child(&branch, Node::new(3));
+    Node::add_child(&root, Node::new(4));
+
+    let values: Vec<i32> = root.iter().collect();
+    println!("tree in pre-order = {values:?}");
+
+    // find hands back an Rc so we can walk up from whatever it finds
+    if let Some(three) = find(&root, 3) {
+        let parent = three.parent.borrow().upgrade().map(|p| p.value);
+        println!("found 3, its parent is {parent:?}");
+    }
+
+    // removing cuts both links, so the removed node no longer points back up
+    if let Some(four) = remove_child(&root, 4) {
+        println!(
+            "removed 4, root has {} children, 4's parent = {:?}",
+            root.children.borrow().len(),
+            four.parent.borrow().upgrade().map(|p| p.value)
+        );
+    }
 }
 
 use std::rc::Weak;
@@ -50,8 +75,226 @@ struct Node {
     children: RefCell<Vec<Rc<Node>>>,
 }
 
+impl Node {
+    fn new(value: i32) -> Rc<Node> {
+        Rc::new(Node {
+            value,
+            parent: RefCell::new(Weak::new()),
+            children: RefCell::new(vec![]),
+        })
+    }
+
+    // Hooks up both directions at once: a strong Rc from parent to child
+    // and a Weak from child back to parent
+    fn add_child(parent: &Rc<Node>, child: Rc<Node>) {
+        *child.parent.borrow_mut() = Rc::downgrade(parent);
+        parent.children.borrow_mut().push(child);
+    }
+
+    // self: &Rc<Node> means this can only be called on a node that is already in an Rc,
+    // which we need since the iterator keeps its own Rc clone of the starting node
+    fn iter(self: &Rc<Node>) -> TreeIter {
+        TreeIter {
+            stack: vec![Rc::clone(self)],
+        }
+    }
+}
+
+// Depth first search for the first node holding target, checking a node before its children.
+// Returns an Rc clone so the caller can keep the node and navigate from it
+fn find(root: &Rc<Node>, target: i32) -> Option<Rc<Node>> {
+    if root.value == target {
+        return Some(Rc::clone(root));
+    }
+
+    // clone the child Rcs out so the borrow of children is dropped before we recurse,
+    // otherwise a borrow_mut anywhere down the tree while searching would panic
+    let children: Vec<Rc<Node>> = root.children.borrow().clone();
+
+    children.iter().find_map(|child| find(child, target))
+}
+
+// Takes the first child with the given value out of parent and hands it back.
+// Both sides of the link get cleaned up so the tree stays consistent:
+// the strong Rc leaves parent.children and the child's Weak parent is reset
+fn remove_child(parent: &Rc<Node>, value: i32) -> Option<Rc<Node>> {
+    let removed = {
+        let mut children = parent.children.borrow_mut();
+        let index = children.iter().position(|child| child.value == value)?;
+        children.remove(index)
+    };
+
+    *removed.parent.borrow_mut() = Weak::new();
+
+    Some(removed)
+}
+
+// Walks the tree in pre-order: a node, then each of its children (left to right) in full.
+// The stack holds Rc clones rather than references so we never hold a borrow of
+// a children RefCell between calls to next()
+struct TreeIter {
+    stack: Vec<Rc<Node>>,
+}
+
+impl Iterator for TreeIter {
+    type Item = i32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+
+        // pushed in reverse so the leftmost child is on top and comes out next
+        // the borrow of children ends at the end of this statement
+        self.stack
+            .extend(node.children.borrow().iter().rev().map(Rc::clone));
+
+        Some(node.value)
+    }
+}
+
+#[cfg(test)]
 mod tests {
     use super::*;
+
+    //        1
+    //      / | \
+    //     2  5  6
+    //    / \     \
+    //   3   4     7
+    fn small_tree() -> Rc<Node> {
+        let root = Node::new(1);
+
+        let two = Node::new(2);
+        Node::add_child(&two, Node::new(3));
+        Node::add_child(&two, Node::new(4));
+
+        let six = Node::new(6);
+        Node::add_child(&six, Node::new(7));
+
+        Node::add_child(&root, two);
+        Node::add_child(&root, Node::new(5));
+        Node::add_child(&root, six);
+
+        root
+    }
+
+    #[test]
+    fn iter_is_pre_order() {
+        let root = small_tree();
+
+        let values: Vec<i32> = root.iter().collect();
+
+        assert_eq!(values, vec![1, 2, 3, 4, 5, 6, 7]);
+    }
+
+    #[test]
+    fn iter_single_node() {
+        let leaf = Node::new(3);
+
+        assert_eq!(leaf.iter().collect::<Vec<i32>>(), vec![3]);
+    }
+
+    #[test]
+    fn iter_from_a_subtree() {
+        let root = small_tree();
+        let two = Rc::clone(&root.children.borrow()[0]);
+
+        assert_eq!(two.iter().collect::<Vec<i32>>(), vec![2, 3, 4]);
+    }
+
+    #[test]
+    fn iter_works_with_adapters() {
+        let root = small_tree();
+
+        let even_sum: i32 = root.iter().filter(|v| v % 2 == 0).sum();
+
+        assert_eq!(even_sum, 2 + 4 + 6);
+    }
+
+    #[test]
+    fn find_deep_node_and_its_parent() {
+        let root = small_tree();
+
+        let seven = find(&root, 7).expect("7 is in the tree");
+        assert_eq!(seven.value, 7);
+
+        let parent = seven.parent.borrow().upgrade().expect("7 has a parent");
+        assert_eq!(parent.value, 6);
+
+        let grandparent = parent.parent.borrow().upgrade().expect("6 has a parent");
+        assert!(Rc::ptr_eq(&grandparent, &root));
+    }
+
+    #[test]
+    fn find_returns_the_same_node_not_a_copy() {
+        let root = small_tree();
+
+        let four = find(&root, 4).unwrap();
+
+        assert!(Rc::ptr_eq(&four, &root.children.borrow()[0].children.borrow()[1]));
+    }
+
+    #[test]
+    fn find_root() {
+        let root = small_tree();
+
+        assert!(Rc::ptr_eq(&find(&root, 1).unwrap(), &root));
+    }
+
+    #[test]
+    fn find_missing_is_none() {
+        let root = small_tree();
+
+        assert!(find(&root, 42).is_none());
+    }
+
+    #[test]
+    fn remove_child_unlinks_both_ways() {
+        let root = small_tree();
+        let five = find(&root, 5).unwrap();
+        assert!(five.parent.borrow().upgrade().is_some());
+
+        let removed = remove_child(&root, 5).expect("5 is a child of root");
+
+        assert!(Rc::ptr_eq(&removed, &five));
+        assert_eq!(root.children.borrow().len(), 2);
+        assert!(removed.parent.borrow().upgrade().is_none());
+        assert_eq!(root.iter().collect::<Vec<i32>>(), vec![1, 2, 3, 4, 6, 7]);
+    }
+
+    #[test]
+    fn remove_child_takes_the_whole_subtree() {
+        let root = small_tree();
+
+        let two = remove_child(&root, 2).unwrap();
+
+        assert_eq!(root.iter().collect::<Vec<i32>>(), vec![1, 5, 6, 7]);
+        // the removed node keeps its own children, and they still point back at it
+        assert_eq!(two.iter().collect::<Vec<i32>>(), vec![2, 3, 4]);
+        let three = find(&two, 3).unwrap();
+        assert!(Rc::ptr_eq(&three.parent.borrow().upgrade().unwrap(), &two));
+    }
+
+    #[test]
+    fn remove_child_only_looks_at_direct_children() {
+        let root = small_tree();
+
+        // 7 is a grandchild, not a child, so nothing happens
+        assert!(remove_child(&root, 7).is_none());
+        assert!(remove_child(&root, 42).is_none());
+        assert_eq!(root.children.borrow().len(), 3);
+    }
+
+    #[test]
+    fn iter_does_not_hold_borrows() {
+        let root = small_tree();
+        let mut iter = root.iter();
+        iter.next();
+
+        // mid iteration we can still mutably borrow the children
+        Node::add_child(&root, Node::new(8));
+
+        assert_eq!(root.children.borrow().len(), 4);
+    }
     fn test_tree_cycles() {
         let leaf = Rc::new(Node {
             value: 3,
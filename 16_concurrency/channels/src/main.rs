@@ -2,6 +2,33 @@ use std::sync::mpsc;
 use std::thread;
 use std::time::Duration;
 
+// Instead of waiting for every sender to be dropped, the producer can tell the consumer to stop
+enum Message {
+    Job(String),
+    Shutdown,
+}
+
+// Handles jobs until it sees Shutdown, then returns what it did.
+// Dropping every sender still ends it too, since recv returns Err once the channel is closed
+fn consume(rx: mpsc::Receiver<Message>) -> Vec<String> {
+    let mut processed = vec![];
+
+    while let Ok(message) = rx.recv() {
+        match message {
+            Message::Job(job) => {
+                println!("Working on: {job}");
+                processed.push(job);
+            }
+            Message::Shutdown => {
+                println!("Shutting down");
+                break;
+            }
+        }
+    }
+
+    processed
+}
+
 fn main() {
     // Create a new channel
     let (tx, rx) = mpsc::channel();
@@ -74,4 +101,82 @@ fn main() {
     for received in rx {
         println!("Got: {received}");
     }
-}
\ No newline at end of file
+
+    // --snip--
+
+    // Create a channel of Messages so we can stop the consumer on demand
+    let (tx, rx) = mpsc::channel();
+
+    // the consumer runs on its own thread and hands back what it processed when joined
+    let consumer = thread::spawn(move || consume(rx));
+
+    for job in ["resize image", "send email"] {
+        tx.send(Message::Job(String::from(job))).unwrap();
+    }
+    tx.send(Message::Shutdown).unwrap();
+
+    // tx is still alive here, it was the Shutdown message that stopped the consumer
+    let processed = consumer.join().unwrap();
+    println!("Processed {} jobs", processed.len());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn processes_all_jobs_before_shutdown() {
+        let (tx, rx) = mpsc::channel();
+        let consumer = thread::spawn(move || consume(rx));
+
+        for i in 1..=5 {
+            tx.send(Message::Job(format!("job {i}"))).unwrap();
+        }
+        tx.send(Message::Shutdown).unwrap();
+
+        // join only returns because of Shutdown, tx hasn't been dropped yet
+        let processed = consumer.join().unwrap();
+
+        assert_eq!(processed, vec!["job 1", "job 2", "job 3", "job 4", "job 5"]);
+        drop(tx);
+    }
+
+    #[test]
+    fn ignores_messages_after_shutdown() {
+        let (tx, rx) = mpsc::channel();
+
+        tx.send(Message::Job(String::from("before"))).unwrap();
+        tx.send(Message::Shutdown).unwrap();
+        tx.send(Message::Job(String::from("after"))).unwrap();
+
+        assert_eq!(consume(rx), vec!["before"]);
+    }
+
+    #[test]
+    fn stops_when_senders_are_dropped() {
+        let (tx, rx) = mpsc::channel();
+
+        tx.send(Message::Job(String::from("only job"))).unwrap();
+        drop(tx);
+
+        // no Shutdown was sent, the closed channel ends it instead
+        assert_eq!(consume(rx), vec!["only job"]);
+    }
+
+    #[test]
+    fn shutdown_from_another_producer() {
+        let (tx, rx) = mpsc::channel();
+        let tx1 = tx.clone();
+        let consumer = thread::spawn(move || consume(rx));
+
+        tx.send(Message::Job(String::from("first"))).unwrap();
+        tx.send(Message::Job(String::from("second"))).unwrap();
+
+        // a different producer can send the Shutdown
+        thread::spawn(move || tx1.send(Message::Shutdown).unwrap())
+            .join()
+            .unwrap();
+
+        assert_eq!(consumer.join().unwrap(), vec!["first", "second"]);
+    }
+}
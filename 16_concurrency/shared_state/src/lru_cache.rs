@@ -0,0 +1,178 @@
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+use std::sync::Mutex;
+
+// Everything the cache needs to change lives behind one Mutex
+// so a get or put is a single lock, and the map and the order can never disagree
+struct Inner<K, V> {
+    capacity: usize,
+    values: HashMap<K, V>,
+    // front is the least recently used key, back is the most recently used
+    order: VecDeque<K>,
+}
+
+impl<K: Eq + Hash + Clone, V> Inner<K, V> {
+    // move key to the back of the order since it was just used
+    fn touch(&mut self, key: &K) {
+        if let Some(index) = self.order.iter().position(|k| k == key) {
+            let key = self.order.remove(index).unwrap();
+            self.order.push_back(key);
+        }
+    }
+}
+
+// A least recently used cache that can be shared between threads
+// Both get and put only need &self since the Mutex gives us interior mutability,
+// so an Arc<LruCache<K, V>> is all a thread needs (no Arc<Mutex<...>> wrapping on the outside)
+pub struct LruCache<K, V> {
+    inner: Mutex<Inner<K, V>>,
+}
+
+// Mutex<T> is Send + Sync as long as T is Send, so LruCache is too when K and V are Send
+impl<K: Eq + Hash + Clone, V: Clone> LruCache<K, V> {
+    // a cache that can hold nothing isnt useful so we panic, like the book's ThreadPool::new
+    pub fn new(capacity: usize) -> LruCache<K, V> {
+        assert!(capacity > 0);
+
+        LruCache {
+            inner: Mutex::new(Inner {
+                capacity,
+                values: HashMap::new(),
+                order: VecDeque::new(),
+            }),
+        }
+    }
+
+    // hands back a clone, a reference couldnt outlive the lock guard
+    // a hit counts as a use so the key moves to the back
+    pub fn get(&self, key: &K) -> Option<V> {
+        let mut inner = self.inner.lock().unwrap();
+
+        let value = inner.values.get(key).cloned()?;
+        inner.touch(key);
+
+        Some(value)
+    }
+
+    // inserting or replacing a key counts as a use, if that puts us over capacity
+    // the key at the front (used longest ago) is evicted
+    pub fn put(&self, key: K, value: V) {
+        let mut inner = self.inner.lock().unwrap();
+
+        if inner.values.insert(key.clone(), value).is_some() {
+            inner.touch(&key);
+            return;
+        }
+
+        inner.order.push_back(key);
+
+        if inner.values.len() > inner.capacity
+            && let Some(oldest) = inner.order.pop_front()
+        {
+            inner.values.remove(&oldest);
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.lock().unwrap().values.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn evicts_least_recently_put() {
+        let cache = LruCache::new(2);
+
+        cache.put("a", 1);
+        cache.put("b", 2);
+        cache.put("c", 3);
+
+        assert_eq!(cache.get(&"a"), None);
+        assert_eq!(cache.get(&"b"), Some(2));
+        assert_eq!(cache.get(&"c"), Some(3));
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn get_counts_as_a_use() {
+        let cache = LruCache::new(2);
+        cache.put("a", 1);
+        cache.put("b", 2);
+
+        // reading a makes b the least recently used
+        assert_eq!(cache.get(&"a"), Some(1));
+        cache.put("c", 3);
+
+        assert_eq!(cache.get(&"b"), None);
+        assert_eq!(cache.get(&"a"), Some(1));
+        assert_eq!(cache.get(&"c"), Some(3));
+    }
+
+    #[test]
+    fn put_existing_key_replaces_and_counts_as_a_use() {
+        let cache = LruCache::new(2);
+        cache.put("a", 1);
+        cache.put("b", 2);
+
+        cache.put("a", 10);
+        cache.put("c", 3);
+
+        assert_eq!(cache.get(&"a"), Some(10));
+        assert_eq!(cache.get(&"b"), None);
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn get_missing_key_is_none() {
+        let cache: LruCache<&str, i32> = LruCache::new(1);
+
+        assert_eq!(cache.get(&"nothing"), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_capacity_panics() {
+        let _cache: LruCache<i32, i32> = LruCache::new(0);
+    }
+
+    #[test]
+    fn is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+
+        assert_send_sync::<LruCache<String, Vec<i32>>>();
+    }
+
+    #[test]
+    fn shared_across_threads() {
+        let cache = Arc::new(LruCache::new(16));
+        let mut handles = vec![];
+
+        for t in 0..8 {
+            let cache = Arc::clone(&cache);
+            let handle = thread::spawn(move || {
+                for i in 0..1000 {
+                    let key = (t * 7 + i) % 32;
+                    cache.put(key, key * 2);
+
+                    // anything we do get back has to be the value stored for that key
+                    if let Some(value) = cache.get(&key) {
+                        assert_eq!(value, key * 2);
+                    }
+                }
+            });
+            handles.push(handle);
+        }
+
+        // a panic in any thread (including a poisoned lock) shows up here
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(cache.len(), 16);
+    }
+}
@@ -1,5 +1,10 @@
 use std::sync::{Arc, Mutex};
 use std::thread;
+use std::time::{Duration, Instant};
+
+mod lru_cache;
+
+use lru_cache::LruCache;
 
 #[cfg(test)]
 mod tests {
@@ -51,9 +56,101 @@ mod tests {
 
         println!("Result: {}", *counter.lock().unwrap());
     }
+
+    #[test]
+    fn philosophers_eat_without_deadlock() {
+        // if the fork order were wrong this could hang forever instead of returning
+        let meals = dining_philosophers(5);
+
+        println!("meals eaten: {meals}");
+        assert!(meals > 0);
+    }
+
+    #[test]
+    fn two_philosophers_share_two_forks() {
+        // the smallest table where both forks are contested by both philosophers
+        assert!(dining_philosophers(2) > 0);
+    }
+
+    #[test]
+    fn lone_philosopher_never_eats() {
+        assert_eq!(dining_philosophers(1), 0);
+        assert_eq!(dining_philosophers(0), 0);
+    }
 }
 
+// how long the philosophers get to eat for
+const MEAL_TIME_BUDGET: Duration = Duration::from_millis(50);
+
+// N philosophers sit around a table with a fork between each pair, eating takes both forks.
+// Each fork is an Arc<Mutex<()>>, the () is because the lock itself is the thing we care about.
+// If everyone grabbed their left fork first they could all end up holding one and waiting on
+// the other forever. Instead everyone picks up the lower numbered fork first, so there is a
+// single global order and someone can always finish eating.
+// Returns how many meals were eaten in total before MEAL_TIME_BUDGET ran out
+fn dining_philosophers(n: usize) -> usize {
+    // with fewer than 2 forks nobody can ever hold two
+    if n < 2 {
+        return 0;
+    }
+
+    let forks: Vec<Arc<Mutex<()>>> = (0..n).map(|_| Arc::new(Mutex::new(()))).collect();
+    let meals = Arc::new(Mutex::new(0));
+    let deadline = Instant::now() + MEAL_TIME_BUDGET;
+
+    let mut handles = vec![];
+
+    for seat in 0..n {
+        let left = seat;
+        let right = (seat + 1) % n;
+        // the last philosopher's right fork is fork 0, so they are the one who reaches right first
+        let first = Arc::clone(&forks[left.min(right)]);
+        let second = Arc::clone(&forks[left.max(right)]);
+        let meals = Arc::clone(&meals);
+
+        handles.push(thread::spawn(move || {
+            while Instant::now() < deadline {
+                let _first = first.lock().unwrap();
+                let _second = second.lock().unwrap();
+
+                // eating
+                *meals.lock().unwrap() += 1;
+
+                // both forks are put down when the guards drop at the end of the loop body
+            }
+        }));
+    }
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    *meals.lock().unwrap()
+}
 
 fn main() {
     println!("Hello, world!");
+
+    // the cache locks internally so threads only need an Arc to share it
+    let cache = Arc::new(LruCache::new(2));
+    let mut handles = vec![];
+
+    for i in 0..3 {
+        let cache = Arc::clone(&cache);
+        handles.push(thread::spawn(move || {
+            cache.put(i, format!("value {i}"));
+        }));
+    }
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    // only 2 of the 3 fit, whichever was put first got evicted
+    println!("cache holds {} entries", cache.len());
+    for i in 0..3 {
+        println!("{i} -> {:?}", cache.get(&i));
+    }
+
+    println!("5 philosophers ate {} meals", dining_philosophers(5));
 }
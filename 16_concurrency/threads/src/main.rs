@@ -59,6 +59,60 @@ mod tests {
         // This will block the main thread until the spawned thread finishes
         handle.join().unwrap();
     }
+
+    #[test]
+    fn barrier_separates_phases() {
+        use std::sync::{Arc, Barrier, Mutex};
+        use std::time::Instant;
+
+        const THREADS: usize = 5;
+
+        // A Barrier blocks each thread that calls wait until THREADS of them have called it,
+        // then lets them all go at once
+        let barrier = Arc::new(Barrier::new(THREADS));
+        // every thread writes (thread id, phase, when) here as it finishes each phase
+        let log = Arc::new(Mutex::new(Vec::new()));
+
+        let mut handles = vec![];
+
+        for id in 0..THREADS {
+            let barrier = Arc::clone(&barrier);
+            let log = Arc::clone(&log);
+
+            handles.push(thread::spawn(move || {
+                // phase 1, threads take different amounts of time to get here
+                thread::sleep(Duration::from_millis(id as u64 * 5));
+                log.lock().unwrap().push((id, 1, Instant::now()));
+
+                barrier.wait();
+
+                // phase 2, nobody gets here until everyone has logged phase 1
+                log.lock().unwrap().push((id, 2, Instant::now()));
+            }));
+        }
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let log = log.lock().unwrap();
+        assert_eq!(log.len(), THREADS * 2);
+
+        // the first THREADS entries are all phase 1 and the rest are all phase 2
+        let (phase_one, phase_two) = log.split_at(THREADS);
+        assert!(phase_one.iter().all(|&(_, phase, _)| phase == 1));
+        assert!(phase_two.iter().all(|&(_, phase, _)| phase == 2));
+
+        // and by the clock, the last phase 1 finished no later than the first phase 2 started
+        let last_phase_one = phase_one.iter().map(|&(_, _, at)| at).max().unwrap();
+        let first_phase_two = phase_two.iter().map(|&(_, _, at)| at).min().unwrap();
+        assert!(last_phase_one <= first_phase_two);
+
+        // every thread made it through both phases
+        for id in 0..THREADS {
+            assert_eq!(log.iter().filter(|&&(t, _, _)| t == id).count(), 2);
+        }
+    }
 }
 
 fn main() {
@@ -1,4 +1,99 @@
+use std::cell::RefCell;
+use std::future::Future;
+use std::sync::Arc;
 use std::time::Duration;
+use trpl::{Either, Receiver, Sender};
+
+// Receives until either every sender is dropped or cancel finishes, whichever happens first.
+// The receive loop is just another future so select can race it against the cancel signal,
+// when cancel wins the loop future is dropped mid await and we stop even though senders are still around
+async fn receive_until_cancelled<C: Future>(rx: &mut Receiver<String>, cancel: C) -> Vec<String> {
+    let mut received = vec![];
+
+    let receive_loop = async {
+        while let Some(value) = rx.recv().await {
+            println!("received '{value}'");
+            received.push(value);
+        }
+    };
+
+    match trpl::select(receive_loop, cancel).await {
+        Either::Left(_) => println!("all senders are gone"),
+        Either::Right(_) => println!("cancelled"),
+    }
+
+    // the loop future was dropped by select so we can use received again
+    received
+}
+
+// Drains every receiver at the same time into one Vec, in the order messages actually arrive.
+// Each receiver gets its own drain future and join_all runs them all until every channel is closed
+async fn fan_in(receivers: Vec<Receiver<String>>) -> Vec<String> {
+    // all the futures run on this one task so a RefCell is enough, and no borrow is held across an await
+    let arrived = RefCell::new(vec![]);
+
+    let drains = receivers.into_iter().map(|mut rx| {
+        let arrived = &arrived;
+        async move {
+            while let Some(value) = rx.recv().await {
+                arrived.borrow_mut().push(value);
+            }
+        }
+    });
+
+    trpl::join_all(drains).await;
+
+    arrived.into_inner()
+}
+
+// Two tasks bump a shared counter, each one `increments` times.
+// This is tokio's Mutex not std's, lock() is a future so a task waiting for the lock hands control
+// back to the runtime instead of blocking the thread, and the guard is fine to hold across an await
+// (holding a std MutexGuard across an await can stall every other task on that thread)
+async fn count_with_two_tasks(increments: u32) -> u32 {
+    let counter = Arc::new(tokio::sync::Mutex::new(0));
+
+    let mut handles = vec![];
+
+    for _ in 0..2 {
+        // spawned tasks can move to other threads, so like thread::spawn they need an Arc
+        let counter = Arc::clone(&counter);
+        handles.push(trpl::spawn_task(async move {
+            for _ in 0..increments {
+                let mut count = counter.lock().await;
+                let current = *count;
+
+                // yield while holding the lock, the other task just waits its turn on lock().await
+                trpl::yield_now().await;
+
+                *count = current + 1;
+            }
+        }));
+    }
+
+    for handle in handles {
+        handle.await.unwrap();
+    }
+
+    *counter.lock().await
+}
+
+// Works through items one at a time (here the "work" is a short sleep and uppercasing),
+// after each one the number finished so far goes down the progress channel so whoever holds
+// the receiver can show how far along we are. progress is dropped at the end which closes the channel
+async fn process_with_progress(items: Vec<String>, progress: Sender<usize>) -> Vec<String> {
+    let mut processed = vec![];
+
+    for (done, item) in items.into_iter().enumerate() {
+        trpl::sleep(Duration::from_millis(10)).await;
+        processed.push(item.to_uppercase());
+
+        // if nobody is watching the progress anymore the work still carries on
+        let _ = progress.send(done + 1);
+    }
+
+    processed
+}
 
 fn main() {
     trpl::block_on(async {
@@ -24,11 +119,19 @@ fn main() {
             }
         };
 
-        // recieve the values
+        // a second channel is our shutdown signal, the first message on it cancels the receiver
+        let (cancel_tx, mut cancel_rx) = trpl::channel::<()>();
+
+        // recieve the values until cancelled, the senders below keep going for longer than this
         let rx_fut = async {
-            while let Some(value) = rx.recv().await {
-                println!("received '{value}'");
-            }
+            let received = receive_until_cancelled(&mut rx, cancel_rx.recv()).await;
+            println!("got {} messages before stopping", received.len());
+        };
+
+        // fire the shutdown signal after a while
+        let cancel_fut = async move {
+            trpl::sleep(Duration::from_millis(2500)).await;
+            cancel_tx.send(()).unwrap();
         };
 
         // now create another async block and move values used (tx)
@@ -48,6 +151,210 @@ fn main() {
         };
 
         // join the futures
-        trpl::join!(tx1_fut, tx_fut, rx_fut);
+        trpl::join!(tx1_fut, tx_fut, rx_fut, cancel_fut);
+
+        // fan in, three channels merged into one list
+        let mut receivers = vec![];
+        let mut senders = vec![];
+        for _ in 0..3 {
+            let (tx, rx) = trpl::channel();
+            senders.push(tx);
+            receivers.push(rx);
+        }
+
+        let send_fut = async move {
+            for (i, tx) in senders.into_iter().enumerate() {
+                tx.send(format!("hello from channel {i}")).unwrap();
+                // tx is dropped here which closes that channel
+            }
+        };
+
+        let (_, merged) = trpl::join(send_fut, fan_in(receivers)).await;
+        println!("fanned in: {merged:?}");
+
+        // async mutex, two tasks sharing one counter
+        println!("counter = {}", count_with_two_tasks(100).await);
+
+        // progress updates come in on a channel while the work is still going
+        let (progress_tx, mut progress_rx) = trpl::channel();
+        let items = vec![String::from("a"), String::from("b"), String::from("c")];
+        let total = items.len();
+
+        let show_progress = async {
+            while let Some(done) = progress_rx.recv().await {
+                println!("processed {done}/{total}");
+            }
+        };
+
+        let (processed, _) =
+            trpl::join(process_with_progress(items, progress_tx), show_progress).await;
+        println!("processed items: {processed:?}");
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cancel_stops_after_two_messages() {
+        trpl::block_on(async {
+            let (tx, mut rx) = trpl::channel();
+            let (cancel_tx, mut cancel_rx) = trpl::channel::<()>();
+
+            let send_fut = async move {
+                tx.send(String::from("one")).unwrap();
+                tx.send(String::from("two")).unwrap();
+                trpl::sleep(Duration::from_millis(50)).await;
+
+                cancel_tx.send(()).unwrap();
+                trpl::sleep(Duration::from_millis(50)).await;
+
+                // tx is still alive and these still send fine, nobody is listening anymore though
+                tx.send(String::from("three")).unwrap();
+                tx.send(String::from("four")).unwrap();
+            };
+
+            let recv_fut = receive_until_cancelled(&mut rx, cancel_rx.recv());
+
+            let ((), received) = trpl::join(send_fut, recv_fut).await;
+
+            assert_eq!(received, vec!["one", "two"]);
+        });
+    }
+
+    #[test]
+    fn ends_normally_when_senders_drop() {
+        trpl::block_on(async {
+            let (tx, mut rx) = trpl::channel();
+
+            tx.send(String::from("only")).unwrap();
+            drop(tx);
+
+            // a cancel that never fires
+            let received = receive_until_cancelled(&mut rx, std::future::pending::<()>()).await;
+
+            assert_eq!(received, vec!["only"]);
+        });
+    }
+
+    #[test]
+    fn cancel_before_anything_is_sent() {
+        trpl::block_on(async {
+            let (tx, mut rx) = trpl::channel::<String>();
+
+            let received = receive_until_cancelled(&mut rx, async {}).await;
+
+            assert!(received.is_empty());
+            drop(tx);
+        });
+    }
+
+    #[test]
+    fn fan_in_collects_every_channel() {
+        trpl::block_on(async {
+            let (tx_a, rx_a) = trpl::channel();
+            let (tx_b, rx_b) = trpl::channel();
+            let (tx_c, rx_c) = trpl::channel();
+
+            // each sender waits its own delays between messages, then drops tx which closes its channel
+            let plans = vec![
+                (tx_a, "a", vec![10, 30, 30]),
+                (tx_b, "b", vec![20, 5]),
+                (tx_c, "c", vec![1, 1, 1, 50]),
+            ];
+            let senders = plans.into_iter().map(|(tx, name, delays)| async move {
+                for (i, delay) in delays.into_iter().enumerate() {
+                    trpl::sleep(Duration::from_millis(delay)).await;
+                    tx.send(format!("{name}{i}")).unwrap();
+                }
+            });
+
+            let (_, merged) =
+                trpl::join(trpl::join_all(senders), fan_in(vec![rx_a, rx_b, rx_c])).await;
+
+            // every message is there no matter how they interleaved
+            let mut sorted = merged.clone();
+            sorted.sort();
+            assert_eq!(
+                sorted,
+                vec!["a0", "a1", "a2", "b0", "b1", "c0", "c1", "c2", "c3"]
+            );
+
+            // and each channel's own messages stay in the order they were sent
+            for name in ["a", "b", "c"] {
+                let from_one: Vec<&String> =
+                    merged.iter().filter(|m| m.starts_with(name)).collect();
+                let mut in_order = from_one.clone();
+                in_order.sort();
+                assert_eq!(from_one, in_order);
+            }
+        });
+    }
+
+    #[test]
+    fn fan_in_with_no_receivers() {
+        trpl::block_on(async {
+            assert!(fan_in(vec![]).await.is_empty());
+        });
+    }
+
+    #[test]
+    fn async_mutex_counts_every_increment() {
+        trpl::block_on(async {
+            // if the read and write could interleave some increments would be lost
+            assert_eq!(count_with_two_tasks(100).await, 200);
+        });
+    }
+
+    #[test]
+    fn async_mutex_with_no_increments() {
+        trpl::block_on(async {
+            assert_eq!(count_with_two_tasks(0).await, 0);
+        });
+    }
+
+    #[test]
+    fn progress_counts_up_to_the_item_count() {
+        trpl::block_on(async {
+            let (tx, mut rx) = trpl::channel();
+            let items: Vec<String> = ["x", "y", "z", "w"].map(String::from).into();
+
+            let collect = async {
+                let mut updates = vec![];
+                // ends once process_with_progress is done and drops the sender
+                while let Some(done) = rx.recv().await {
+                    updates.push(done);
+                }
+                updates
+            };
+
+            let (processed, updates) = trpl::join(process_with_progress(items, tx), collect).await;
+
+            assert_eq!(updates, vec![1, 2, 3, 4]);
+            assert_eq!(processed, vec!["X", "Y", "Z", "W"]);
+        });
+    }
+
+    #[test]
+    fn progress_keeps_going_without_a_receiver() {
+        trpl::block_on(async {
+            let (tx, rx) = trpl::channel();
+            drop(rx);
+
+            let processed = process_with_progress(vec![String::from("a")], tx).await;
+
+            assert_eq!(processed, vec!["A"]);
+        });
+    }
+
+    #[test]
+    fn no_items_no_progress() {
+        trpl::block_on(async {
+            let (tx, mut rx) = trpl::channel();
+
+            assert!(process_with_progress(vec![], tx).await.is_empty());
+            assert_eq!(rx.recv().await, None);
+        });
+    }
+}
@@ -1,5 +1,13 @@
+use scraper::Selector;
+use std::collections::HashSet;
+use std::future::Future;
+use std::time::Duration;
 use trpl::{Either, Html};
 
+// the most pages crawl will fetch at the same time, so a page with hundreds of links
+// turns into a few requests at a time instead of hundreds at once
+const MAX_CONCURRENT_FETCHES: usize = 4;
+
 async fn page_title(url: &str) -> Option<String> {
     // we await the url
     let response = trpl::get(url).await;
@@ -13,6 +21,185 @@ async fn page_title(url: &str) -> Option<String> {
         .map(|title| title.inner_html())
 }
 
+// Same timeout as in numerous_futures, whichever of the future and the sleep finishes first wins
+async fn timeout<F: Future>(future_to_try: F, max_time: Duration) -> Result<F::Output, Duration> {
+    match trpl::select(future_to_try, trpl::sleep(max_time)).await {
+        Either::Left(output) => Ok(output),
+        Either::Right(_) => Err(max_time),
+    }
+}
+
+// Fetches every url at once and hands back the url and title of the first page to come back
+// with a title, None if none of them manage that before limit runs out
+async fn first_title_within(urls: Vec<String>, limit: Duration) -> Option<(String, String)> {
+    first_title_within_using(urls, limit, |url| async move { page_title(&url).await }).await
+}
+
+// The race itself, get_title is passed in so a test can control how long each page takes
+async fn first_title_within_using<F, Fut>(
+    urls: Vec<String>,
+    limit: Duration,
+    get_title: F,
+) -> Option<(String, String)>
+where
+    F: Fn(String) -> Fut,
+    Fut: Future<Output = Option<String>>,
+{
+    // each fetch sends its title down the channel as soon as it has one
+    let (tx, mut rx) = trpl::channel();
+    let fetches: Vec<_> = urls
+        .into_iter()
+        .map(|url| {
+            let tx = tx.clone();
+            let title = get_title(url.clone());
+
+            async move {
+                if let Some(title) = title.await {
+                    // the receiver is gone once someone else has won, that is fine
+                    let _ = tx.send((url, title));
+                }
+            }
+        })
+        .collect();
+    // only the fetches hold a sender now, so recv gives None once they have all finished
+    drop(tx);
+
+    let race = async {
+        match trpl::select(rx.recv(), trpl::join_all(fetches)).await {
+            Either::Left(first) => first,
+            // every fetch finished in the same poll, anything they sent is still in the channel
+            Either::Right(_) => rx.recv().await,
+        }
+    };
+
+    timeout(race, limit).await.ok().flatten()
+}
+
+// The useful bits of a page, anything the page doesnt have is None (or empty for headings)
+#[derive(Debug, PartialEq, Default)]
+struct PageMeta {
+    title: Option<String>,
+    description: Option<String>,
+    h1: Vec<String>,
+}
+
+// like page_title but gathers more than the <title>
+async fn page_metadata(url: &str) -> PageMeta {
+    let response_text = trpl::get(url).await.text().await;
+
+    parse_metadata(&response_text)
+}
+
+// The parsing half of page_metadata, kept apart from the fetch so it can be tested on a fixed string
+fn parse_metadata(page: &str) -> PageMeta {
+    let html = scraper::Html::parse_document(page);
+    let title = Selector::parse("title").unwrap();
+    let description = Selector::parse(r#"meta[name="description"]"#).unwrap();
+    let h1 = Selector::parse("h1").unwrap();
+
+    // text() gives every piece of text inside the element, even inside nested tags like <em>
+    let text_of =
+        |element: scraper::ElementRef| element.text().collect::<String>().trim().to_string();
+
+    PageMeta {
+        title: html.select(&title).next().map(text_of),
+        description: html
+            .select(&description)
+            .next()
+            .and_then(|meta| meta.value().attr("content"))
+            .map(|content| content.trim().to_string()),
+        h1: html.select(&h1).map(text_of).collect(),
+    }
+}
+
+// Follows links out from start_url, max_depth is how many links away from it we go
+// (0 is just the start page), returns every url that was fetched in the order we got to them
+async fn crawl(start_url: &str, max_depth: usize) -> Vec<String> {
+    crawl_with(start_url, max_depth, |url| async move {
+        trpl::get(&url).await.text().await
+    })
+    .await
+}
+
+// The crawl itself, fetch turns a url into the text of the page
+// it is passed in so a test can serve pages from memory instead of the network
+// We go one depth at a time, every page at the current depth is fetched (a few at a time)
+// and any link we havent seen before becomes part of the next depth
+async fn crawl_with<F, Fut>(start_url: &str, max_depth: usize, fetch: F) -> Vec<String>
+where
+    F: Fn(String) -> Fut,
+    Fut: Future<Output = String>,
+{
+    let mut visited = vec![];
+    let mut seen = HashSet::from([start_url.to_string()]);
+    let mut level = vec![start_url.to_string()];
+
+    for depth in 0..=max_depth {
+        let mut next_level = vec![];
+
+        for batch in level.chunks(MAX_CONCURRENT_FETCHES) {
+            let pages = trpl::join_all(batch.iter().map(|url| fetch(url.clone()))).await;
+
+            for (url, page) in batch.iter().zip(pages) {
+                visited.push(url.clone());
+
+                // links on the deepest pages would only lead past the limit
+                if depth == max_depth {
+                    continue;
+                }
+
+                for link in extract_links(url, &page) {
+                    // insert is false if the url was already in the set
+                    if seen.insert(link.clone()) {
+                        next_level.push(link);
+                    }
+                }
+            }
+        }
+
+        if next_level.is_empty() {
+            break;
+        }
+        level = next_level;
+    }
+
+    visited
+}
+
+// every <a href> on the page that resolve understands
+fn extract_links(page_url: &str, page: &str) -> Vec<String> {
+    let selector = Selector::parse("a[href]").unwrap();
+
+    scraper::Html::parse_document(page)
+        .select(&selector)
+        .filter_map(|a| a.value().attr("href"))
+        .filter_map(|href| resolve(page_url, href))
+        .collect()
+}
+
+// Turns an href into a full url, only absolute http(s) links and links starting with /
+// (relative to the site the page is on) are followed, anything else (mailto:, ../page) is skipped
+// the #fragment is dropped since it is the same page either way
+fn resolve(page_url: &str, href: &str) -> Option<String> {
+    let href = href.split('#').next()?;
+
+    if href.starts_with("http://") || href.starts_with("https://") {
+        return Some(href.to_string());
+    }
+
+    if href.starts_with('/') && !href.starts_with("//") {
+        // the origin is everything up to the first / after the scheme
+        let after_scheme = page_url.find("://")? + 3;
+        let origin_end = page_url[after_scheme..]
+            .find('/')
+            .map_or(page_url.len(), |i| after_scheme + i);
+
+        return Some(format!("{}{href}", &page_url[..origin_end]));
+    }
+
+    None
+}
+
 fn main() {
     // collect cli arguments
     let args: Vec<String> = std::env::args().collect();
@@ -35,5 +222,222 @@ fn main() {
             Some(title) => println!("Its page title was: '{title}'"),
             None => println!("It had no title."),
         }
+
+        // grab a bit more than the title from the first page
+        let meta = page_metadata(&args[1]).await;
+        println!("{} metadata: {meta:?}", &args[1]);
+
+        // race every url given against a shared 5 second deadline
+        match first_title_within(args[1..].to_vec(), Duration::from_secs(5)).await {
+            Some((url, title)) => println!("{url} was the first with a title: '{title}'"),
+            None => println!("No page had a title within 5 seconds"),
+        }
+
+        // then follow the links on the first page one level deep
+        let pages = crawl(&args[1], 1).await;
+        println!("Crawled {} pages starting from {}", pages.len(), &args[1]);
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::collections::HashMap;
+    use std::time::Duration;
+
+    // a tiny site, a links to b and c, b links back to a and on to d, d links to e
+    fn site() -> HashMap<&'static str, &'static str> {
+        HashMap::from([
+            (
+                "https://site.test/a",
+                r#"<a href="/b">b</a> <a href="https://site.test/c">c</a>"#,
+            ),
+            (
+                "https://site.test/b",
+                r#"<a href="/a">back</a> <a href="/d#top">d</a>"#,
+            ),
+            ("https://site.test/c", r#"<a href="/b">b again</a>"#),
+            ("https://site.test/d", r#"<a href="/e">e</a>"#),
+            ("https://site.test/e", "the end"),
+        ])
+    }
+
+    fn crawl_site(max_depth: usize) -> Vec<String> {
+        let pages = site();
+
+        trpl::block_on(crawl_with("https://site.test/a", max_depth, |url| {
+            let page = pages.get(url.as_str()).copied().unwrap_or_default();
+            async move { page.to_string() }
+        }))
+    }
+
+    #[test]
+    fn depth_zero_is_only_the_start_page() {
+        assert_eq!(crawl_site(0), vec!["https://site.test/a"]);
+    }
+
+    #[test]
+    fn stops_at_max_depth() {
+        assert_eq!(
+            crawl_site(1),
+            vec![
+                "https://site.test/a",
+                "https://site.test/b",
+                "https://site.test/c"
+            ]
+        );
+        // e is three links away so depth 2 stops at d
+        assert_eq!(
+            crawl_site(2).last().map(|s| s.as_str()),
+            Some("https://site.test/d")
+        );
+    }
+
+    #[test]
+    fn each_page_is_visited_once() {
+        let visited = crawl_site(10);
+
+        // b is linked from a and c and a is linked back from b, but each shows up once
+        assert_eq!(visited.len(), 5);
+        assert_eq!(visited.iter().collect::<HashSet<_>>().len(), 5);
+    }
+
+    #[test]
+    fn never_fetches_more_than_the_limit_at_once() {
+        let links: String = (0..10)
+            .map(|i| format!(r#"<a href="/page{i}">{i}</a>"#))
+            .collect();
+        let in_flight = &Cell::new(0);
+        let most_in_flight = &Cell::new(0);
+
+        let visited = trpl::block_on(crawl_with("https://site.test/", 1, |url| {
+            let page = if url == "https://site.test/" {
+                links.clone()
+            } else {
+                String::new()
+            };
+
+            async move {
+                in_flight.set(in_flight.get() + 1);
+                most_in_flight.set(most_in_flight.get().max(in_flight.get()));
+                trpl::sleep(Duration::from_millis(1)).await;
+                in_flight.set(in_flight.get() - 1);
+                page
+            }
+        }));
+
+        assert_eq!(visited.len(), 11);
+        assert_eq!(most_in_flight.get(), MAX_CONCURRENT_FETCHES);
+    }
+
+    #[test]
+    fn parses_every_metadata_field() {
+        let page = r#"
+            <html>
+                <head>
+                    <title> Rust Book </title>
+                    <meta name="viewport" content="width=device-width">
+                    <meta name="description" content="Learning Rust one chapter at a time">
+                </head>
+                <body>
+                    <h1>Async</h1>
+                    <p>some text</p>
+                    <h1>Streams and <em>Futures</em></h1>
+                </body>
+            </html>
+        "#;
+
+        assert_eq!(
+            parse_metadata(page),
+            PageMeta {
+                title: Some(String::from("Rust Book")),
+                description: Some(String::from("Learning Rust one chapter at a time")),
+                h1: vec![String::from("Async"), String::from("Streams and Futures")],
+            }
+        );
+    }
+
+    #[test]
+    fn missing_metadata_is_empty() {
+        assert_eq!(parse_metadata("<p>nothing here</p>"), PageMeta::default());
+    }
+
+    // every fake page takes ms milliseconds to answer with title
+    fn race(pages: &[(&str, u64, Option<&str>)], limit_ms: u64) -> Option<(String, String)> {
+        let urls = pages.iter().map(|(url, _, _)| url.to_string()).collect();
+
+        trpl::block_on(first_title_within_using(
+            urls,
+            Duration::from_millis(limit_ms),
+            |url| {
+                let (_, ms, title) = *pages.iter().find(|(u, _, _)| *u == url).unwrap();
+                async move {
+                    trpl::sleep(Duration::from_millis(ms)).await;
+                    title.map(String::from)
+                }
+            },
+        ))
+    }
+
+    #[test]
+    fn fastest_page_wins() {
+        let winner = race(
+            &[
+                ("slow", 300, Some("Slow")),
+                ("fast", 10, Some("Fast")),
+                ("slower", 400, Some("Slower")),
+            ],
+            200,
+        );
+
+        assert_eq!(winner, Some((String::from("fast"), String::from("Fast"))));
+    }
+
+    #[test]
+    fn page_without_a_title_doesnt_win() {
+        let winner = race(
+            &[("untitled", 5, None), ("titled", 30, Some("Titled"))],
+            200,
+        );
+
+        assert_eq!(
+            winner,
+            Some((String::from("titled"), String::from("Titled")))
+        );
+    }
+
+    #[test]
+    fn none_when_everything_times_out() {
+        assert_eq!(
+            race(&[("a", 300, Some("A")), ("b", 400, Some("B"))], 50),
+            None
+        );
+    }
+
+    #[test]
+    fn none_when_no_page_has_a_title() {
+        assert_eq!(race(&[("a", 5, None), ("b", 10, None)], 200), None);
+        assert_eq!(race(&[], 200), None);
+    }
+
+    #[test]
+    fn resolves_links() {
+        let page = "https://site.test/docs/page";
+
+        assert_eq!(
+            resolve(page, "/other"),
+            Some(String::from("https://site.test/other"))
+        );
+        assert_eq!(
+            resolve(page, "http://else.test/x#y"),
+            Some(String::from("http://else.test/x"))
+        );
+        assert_eq!(
+            resolve("https://site.test", "/a"),
+            Some(String::from("https://site.test/a"))
+        );
+        assert_eq!(resolve(page, "mailto:me@site.test"), None);
+        assert_eq!(resolve(page, "../up"), None);
+    }
+}
@@ -1,7 +1,12 @@
 use trpl::Either;
 use std::time::Duration;
-use std::future::Future;
+use std::future::{self, Future};
+use std::task::Poll;
 use std::thread;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::hash::Hash;
+use futures::future::{FutureExt, LocalBoxFuture, Shared};
 
 // Timeout function
 async fn timeout<F: Future>(
@@ -20,6 +25,86 @@ fn slow(name: &str, ms: u64) {
     println!("'{name}' ran for {ms}ms");
 }
 
+// Like the Cacher from the closures chapter but for an async calculation.
+// What gets stored is the Shared future itself rather than the finished value, so a second caller
+// for a key that is still being worked on awaits the same in flight future instead of starting it again.
+// Once it completes, Shared hangs onto the output so later callers get it straight away.
+// The calculation is boxed so AsyncCacher only needs K and V as type parameters
+struct AsyncCacher<K, V> {
+    calculation: Box<dyn Fn(K) -> LocalBoxFuture<'static, V>>,
+    // RefCell so value can take &self, two callers awaiting at the same time both need to borrow it
+    values: RefCell<HashMap<K, Shared<LocalBoxFuture<'static, V>>>>,
+}
+
+impl<K, V> AsyncCacher<K, V>
+where
+    K: Eq + Hash + Clone,
+    // every caller gets their own copy of the output so it has to be Clone
+    V: Clone + 'static,
+{
+    fn new<F, Fut>(calculation: F) -> AsyncCacher<K, V>
+    where
+        F: Fn(K) -> Fut + 'static,
+        Fut: Future<Output = V> + 'static,
+    {
+        AsyncCacher {
+            calculation: Box::new(move |key| calculation(key).boxed_local()),
+            values: RefCell::new(HashMap::new()),
+        }
+    }
+
+    async fn value(&self, key: K) -> V {
+        // clone the Shared handle out so the RefCell borrow ends before we await
+        let shared = {
+            let mut values = self.values.borrow_mut();
+            values
+                .entry(key.clone())
+                .or_insert_with(|| (self.calculation)(key).shared())
+                .clone()
+        };
+
+        shared.await
+    }
+}
+
+// Runs every future at the same time and splits what they return into the successes and failures
+// unlike ? (or try_join) an Err doesnt stop anything, every future still runs to the end
+// both Vecs keep the order the futures were passed in
+async fn join_results<T, E>(futures: Vec<impl Future<Output = Result<T, E>>>) -> (Vec<T>, Vec<E>) {
+    let mut successes = vec![];
+    let mut failures = vec![];
+
+    for result in trpl::join_all(futures).await {
+        match result {
+            Ok(value) => successes.push(value),
+            Err(e) => failures.push(e),
+        }
+    }
+
+    (successes, failures)
+}
+
+// trpl::select for any number of futures, returns the index of the one that finished first and its output
+// poll_fn lets us write the polling by hand: each time we are woken we poll every future in order,
+// so if two are ready at once the lower index wins. Box::pin is needed since a future has to be
+// pinned before it can be polled. An empty Vec would never finish so we panic on it instead
+async fn select_any<T>(futures: Vec<impl Future<Output = T>>) -> (usize, T) {
+    assert!(!futures.is_empty(), "select_any needs at least one future");
+
+    let mut futures: Vec<_> = futures.into_iter().map(Box::pin).collect();
+
+    future::poll_fn(|cx| {
+        for (index, future) in futures.iter_mut().enumerate() {
+            if let Poll::Ready(output) = future.as_mut().poll(cx) {
+                return Poll::Ready((index, output));
+            }
+        }
+
+        Poll::Pending
+    })
+    .await
+}
+
 fn main() {
     // Use block_on to initalize a runtime
     trpl::block_on(async {
@@ -63,5 +148,166 @@ fn main() {
                 println!("Failed after {} seconds", duration.as_secs())
             }
         }
+
+        // two requests for the same key at the same time, the slow lookup only happens once
+        let lookups = AsyncCacher::new(|id: u32| async move {
+            println!("looking up {id}...");
+            trpl::sleep(Duration::from_millis(500)).await;
+            format!("user #{id}")
+        });
+
+        let (first, second) = trpl::join(lookups.value(7), lookups.value(7)).await;
+        println!("got '{first}' and '{second}'");
+
+        // a batch of parses where some fail, we still get every result back
+        let parses = ["1", "two", "3", "four"].map(|input| async move { input.parse::<u32>() });
+        let (numbers, errors) = join_results(parses.into()).await;
+        println!("parsed {numbers:?}, {} failed", errors.len());
+
+        // race three sleeps, the 100ms one at index 1 wins
+        let sleeps = [300, 100, 200].map(|ms| async move {
+            trpl::sleep(Duration::from_millis(ms)).await;
+            ms
+        });
+        let (index, ms) = select_any(sleeps.into()).await;
+        println!("future {index} finished first after {ms}ms");
     });
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    // a cacher around a slow doubling function, runs counts how many times it actually ran
+    fn doubler() -> (AsyncCacher<u32, u32>, Rc<Cell<u32>>) {
+        let runs = Rc::new(Cell::new(0));
+        let counter = Rc::clone(&runs);
+
+        let cacher = AsyncCacher::new(move |n: u32| {
+            let counter = Rc::clone(&counter);
+            async move {
+                counter.set(counter.get() + 1);
+                trpl::sleep(Duration::from_millis(20)).await;
+                n * 2
+            }
+        });
+
+        (cacher, runs)
+    }
+
+    #[test]
+    fn concurrent_callers_share_one_run() {
+        let (cacher, runs) = doubler();
+
+        let (a, b) = trpl::block_on(trpl::join(cacher.value(21), cacher.value(21)));
+
+        assert_eq!((a, b), (42, 42));
+        assert_eq!(runs.get(), 1);
+    }
+
+    #[test]
+    fn later_callers_get_the_cached_value() {
+        let (cacher, runs) = doubler();
+
+        trpl::block_on(async {
+            assert_eq!(cacher.value(5).await, 10);
+            assert_eq!(cacher.value(5).await, 10);
+        });
+
+        assert_eq!(runs.get(), 1);
+    }
+
+    #[test]
+    fn different_keys_each_run() {
+        let (cacher, runs) = doubler();
+
+        let (a, b) = trpl::block_on(trpl::join(cacher.value(1), cacher.value(2)));
+
+        assert_eq!((a, b), (2, 4));
+        assert_eq!(runs.get(), 2);
+    }
+
+    #[test]
+    fn join_results_partitions_everything() {
+        let finished = Cell::new(0);
+
+        // the errors finish first, the successes are still waited for
+        let jobs: Vec<_> = (1..=6)
+            .map(|n| {
+                let finished = &finished;
+                async move {
+                    let ms = if n % 2 == 0 { 5 } else { 20 };
+                    trpl::sleep(Duration::from_millis(ms)).await;
+                    finished.set(finished.get() + 1);
+
+                    if n % 2 == 0 { Err(format!("{n} failed")) } else { Ok(n) }
+                }
+            })
+            .collect();
+
+        let (successes, failures) = trpl::block_on(join_results(jobs));
+
+        assert_eq!(successes, vec![1, 3, 5]);
+        assert_eq!(failures, vec!["2 failed", "4 failed", "6 failed"]);
+        assert_eq!(finished.get(), 6);
+    }
+
+    #[test]
+    fn join_results_runs_concurrently() {
+        let jobs: Vec<_> = (0..5)
+            .map(|n| async move {
+                trpl::sleep(Duration::from_millis(50)).await;
+                Ok::<u32, String>(n)
+            })
+            .collect();
+
+        let start = std::time::Instant::now();
+        let (successes, failures) = trpl::block_on(join_results(jobs));
+
+        // one after another this would take 250ms
+        assert!(start.elapsed() < Duration::from_millis(200));
+        assert_eq!(successes, vec![0, 1, 2, 3, 4]);
+        assert!(failures.is_empty());
+    }
+
+    #[test]
+    fn join_results_with_no_futures() {
+        let jobs: Vec<std::future::Ready<Result<u32, String>>> = vec![];
+
+        assert_eq!(trpl::block_on(join_results(jobs)), (vec![], vec![]));
+    }
+
+    async fn sleep_for(ms: u64) -> u64 {
+        trpl::sleep(Duration::from_millis(ms)).await;
+        ms
+    }
+
+    #[test]
+    fn select_any_returns_the_shortest() {
+        let sleeps = vec![sleep_for(80), sleep_for(40), sleep_for(10), sleep_for(60)];
+
+        assert_eq!(trpl::block_on(select_any(sleeps)), (2, 10));
+    }
+
+    #[test]
+    fn select_any_tie_goes_to_lower_index() {
+        let ready = vec![future::ready("first"), future::ready("second")];
+
+        assert_eq!(trpl::block_on(select_any(ready)), (0, "first"));
+    }
+
+    #[test]
+    fn select_any_with_one_future() {
+        assert_eq!(trpl::block_on(select_any(vec![sleep_for(1)])), (0, 1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn select_any_with_nothing_panics() {
+        let nothing: Vec<future::Ready<()>> = vec![];
+
+        trpl::block_on(select_any(nothing));
+    }
+}
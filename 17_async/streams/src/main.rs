@@ -1,5 +1,114 @@
+use std::io;
+use std::mem;
+use std::path::Path;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio_stream::{Stream, wrappers::LinesStream};
 use trpl::StreamExt;
 
+// Streams a file one line at a time instead of reading the whole thing first.
+// Opening can fail (file not found etc) so that error comes back straight away as the outer Result,
+// after that each line is its own Result since reading can still fail part way through
+async fn lines_from_file(
+    path: impl AsRef<Path>,
+) -> io::Result<impl Stream<Item = io::Result<String>>> {
+    let file = tokio::fs::File::open(path).await?;
+
+    // lines() gives us something we can call next_line on, LinesStream turns that into a Stream
+    Ok(LinesStream::new(BufReader::new(file).lines()))
+}
+
+// Groups a stream into Vecs of `size` items, whatever is left over at the end comes out as a shorter last batch.
+// It only pulls from the inner stream when someone asks for the next batch, so a slow consumer
+// slows the producer down too instead of everything piling up in memory (backpressure)
+fn batch_stream<S: Stream>(stream: S, size: usize) -> impl Stream<Item = Vec<S::Item>> {
+    // a batch of 0 would never fill up
+    assert!(size > 0);
+
+    Batch {
+        stream: Box::pin(stream),
+        size,
+        buffer: Vec::with_capacity(size),
+        done: false,
+    }
+}
+
+struct Batch<S: Stream> {
+    // the inner stream is pinned on the heap so moving Batch never moves it
+    stream: Pin<Box<S>>,
+    size: usize,
+    buffer: Vec<S::Item>,
+    // the inner stream shouldnt be polled again once it has returned None
+    done: bool,
+}
+
+// Batch never hands out a pinned reference to any of its own fields (the stream is already pinned in
+// its Box) so it's fine for Batch to be Unpin, which lets poll_next get a plain &mut to its fields.
+// Without this the compiler only makes it Unpin when the items are
+impl<S: Stream> Unpin for Batch<S> {}
+
+impl<S: Stream> Stream for Batch<S> {
+    type Item = Vec<S::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        if this.done {
+            return Poll::Ready(None);
+        }
+
+        // keep pulling items until the batch is full, the stream ends, or it has nothing ready yet
+        loop {
+            match this.stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(item)) => {
+                    this.buffer.push(item);
+
+                    if this.buffer.len() == this.size {
+                        let batch = mem::replace(&mut this.buffer, Vec::with_capacity(this.size));
+                        return Poll::Ready(Some(batch));
+                    }
+                }
+                Poll::Ready(None) => {
+                    this.done = true;
+
+                    // hand out the partial batch if there is one
+                    return if this.buffer.is_empty() {
+                        Poll::Ready(None)
+                    } else {
+                        Poll::Ready(Some(mem::take(&mut this.buffer)))
+                    };
+                }
+                // the inner stream has already arranged for us to be woken, the buffer keeps what we have so far
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+// Emits 1, 2, 3... one every period, forever.
+// A task sleeps and sends on a channel in a loop, and the receiving end is wrapped up as the stream.
+// Once whoever has the stream drops it the send fails and the task stops
+fn interval_stream(period: Duration) -> impl Stream<Item = u32> {
+    let (tx, rx) = trpl::channel();
+
+    // spawn_task means it keeps ticking on its own, it has to be called inside block_on
+    trpl::spawn_task(async move {
+        let mut count = 0;
+        loop {
+            trpl::sleep(period).await;
+            count += 1;
+
+            if tx.send(count).is_err() {
+                break;
+            }
+        }
+    });
+
+    trpl::ReceiverStream::new(rx)
+}
+
 fn main() {
     trpl::block_on(async {
         // create an array of values
@@ -15,5 +124,146 @@ fn main() {
         while let Some(value) = stream.next().await {
             println!("The value was: {value}");
     }
+
+        // group a stream into batches of 3
+        let mut batches = batch_stream(trpl::stream_from_iter(1..=10), 3);
+        while let Some(batch) = batches.next().await {
+            println!("Batch: {batch:?}");
+        }
+
+        // ticks on a timer, the stream never ends by itself so take limits it
+        let start = Instant::now();
+        let mut ticks = interval_stream(Duration::from_millis(100)).take(5);
+        while let Some(tick) = ticks.next().await {
+            println!("Tick {tick} at {}ms", start.elapsed().as_millis());
+        }
+
+        // a stream backed by real IO, this file's own source
+        match lines_from_file("src/main.rs").await {
+            Ok(mut lines) => {
+                let mut count = 0;
+                while let Some(Ok(_line)) = lines.next().await {
+                    count += 1;
+                }
+                println!("src/main.rs has {count} lines");
+            }
+            Err(e) => println!("Couldn't open src/main.rs: {e}"),
+        }
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{env, fs, process};
+
+    #[test]
+    fn streams_lines_from_a_file() {
+        let path = env::temp_dir().join(format!("streams_lines_{}.txt", process::id()));
+        fs::write(&path, "first line\nsecond line\n\nlast line").unwrap();
+
+        let lines: Vec<String> = trpl::block_on(async {
+            let stream = lines_from_file(&path).await.unwrap();
+            stream.map(|line| line.unwrap()).collect().await
+        });
+
+        fs::remove_file(&path).unwrap();
+
+        // the blank line is kept and the missing trailing newline doesnt matter
+        assert_eq!(lines, vec!["first line", "second line", "", "last line"]);
+    }
+
+    #[test]
+    fn missing_file_is_an_error() {
+        let path = env::temp_dir().join(format!("streams_missing_{}.txt", process::id()));
+
+        let result = trpl::block_on(lines_from_file(&path));
+
+        match result {
+            Err(e) => assert_eq!(e.kind(), io::ErrorKind::NotFound),
+            Ok(_) => panic!("expected the open to fail"),
+        }
+    }
+
+    #[test]
+    fn batches_of_three_with_a_partial_last_batch() {
+        let batches: Vec<Vec<i32>> =
+            trpl::block_on(batch_stream(trpl::stream_from_iter(1..=10), 3).collect());
+
+        assert_eq!(
+            batches,
+            vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9], vec![10]]
+        );
+    }
+
+    #[test]
+    fn exact_multiple_has_no_empty_last_batch() {
+        let batches: Vec<Vec<i32>> =
+            trpl::block_on(batch_stream(trpl::stream_from_iter(1..=4), 2).collect());
+
+        assert_eq!(batches, vec![vec![1, 2], vec![3, 4]]);
+    }
+
+    #[test]
+    fn empty_stream_has_no_batches() {
+        let batches: Vec<Vec<i32>> =
+            trpl::block_on(batch_stream(trpl::stream_from_iter(Vec::<i32>::new()), 3).collect());
+
+        assert!(batches.is_empty());
+    }
+
+    #[test]
+    fn batches_items_that_arrive_over_time() {
+        // items trickle in from a channel, batches still come out full
+        let batches: Vec<Vec<u64>> = trpl::block_on(async {
+            let (tx, rx) = trpl::channel();
+
+            let send_fut = async move {
+                for i in 1..=5 {
+                    tx.send(i).unwrap();
+                    trpl::sleep(Duration::from_millis(5)).await;
+                }
+            };
+
+            let batched = batch_stream(trpl::ReceiverStream::new(rx), 2).collect::<Vec<_>>();
+
+            trpl::join(send_fut, batched).await.1
+        });
+
+        assert_eq!(batches, vec![vec![1, 2], vec![3, 4], vec![5]]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_size_panics() {
+        let _ = batch_stream(trpl::stream_from_iter(1..=3), 0);
+    }
+
+    #[test]
+    fn interval_ticks_count_up() {
+        let ticks: Vec<u32> = trpl::block_on(async {
+            interval_stream(Duration::from_millis(5))
+                .take(4)
+                .collect()
+                .await
+        });
+
+        assert_eq!(ticks, vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn three_ticks_take_at_least_two_periods() {
+        let period = Duration::from_millis(50);
+
+        let elapsed = trpl::block_on(async {
+            let start = Instant::now();
+            let ticks: Vec<u32> = interval_stream(period).take(3).collect().await;
+            assert_eq!(ticks.len(), 3);
+            start.elapsed()
+        });
+
+        // really it's about 3 periods since the first tick waits too,
+        // only checking the lower bound keeps this from being flaky on a busy machine
+        assert!(elapsed >= period * 2, "only took {elapsed:?}");
+    }
+}
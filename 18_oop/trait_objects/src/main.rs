@@ -1,5 +1,7 @@
 
-use gui::{Draw, Button, Screen};
+use gui::{Draw, Button, Screen, StaticScreen};
+use observer::{Observer, Subject};
+use blog::Post;
 
 fn main() {
     // define the screen and its subsequent components
@@ -14,16 +16,62 @@ fn main() {
                     String::from("No"),
                 ],
             }),
-            Box::new(Button {
-                width: 50,
-                height: 10,
-                label: String::from("OK"),
-            }),
+            Box::new(Button::new(50, 10, String::from("OK"))),
         ],
     };
 
     // call run on the screen to draw components
     screen.run();
+
+    // screens can be nested since Screen implements Draw itself
+    let dialog = Screen {
+        components: vec![
+            Box::new(Button::new(50, 10, String::from("Back"))),
+            Box::new(Screen {
+                components: vec![Box::new(Button::new(50, 10, String::from("Confirm")))],
+            }),
+        ],
+    };
+    dialog.run();
+
+    // when every component is the same type we can use generics instead,
+    // the compiler knows it's always a Button so no vtable lookup is needed
+    let toolbar = StaticScreen {
+        components: vec![
+            Button::new(50, 10, String::from("Save")),
+            Button::new(50, 10, String::from("Cancel")),
+        ],
+    };
+    toolbar.run();
+    for button in toolbar.components.iter() {
+        println!("{} drawn {} time(s)", button.label, button.times_drawn());
+    }
+
+    // same trait object idea but for events, the subject doesnt know what its observers are
+    let mut subject = Subject::new();
+    subject.subscribe(Box::new(PrintObserver {
+        name: String::from("logger"),
+    }));
+    subject.emit("screen drawn");
+
+    // the post only shows its content once it has gone through review
+    let mut post = Post::new();
+    post.add_text("I ate a salad for lunch today");
+    post.request_review();
+    println!("before approval: {:?}", post.content());
+    post.approve();
+    println!("after approval: {:?}", post.content());
+}
+
+struct PrintObserver {
+    // observer that just prints whatever it is told about
+    name: String,
+}
+
+impl Observer for PrintObserver {
+    fn notify(&self, event: &str) {
+        println!("{} got event: {}", self.name, event);
+    }
 }
 
 struct SelectBox {
@@ -40,6 +88,8 @@ impl Draw for SelectBox {
 }
 
 mod gui {
+    use std::cell::Cell;
+
     pub trait Draw {
         // Our Draw trait requires those implementing it to implement draw
         fn draw(&self);
@@ -52,7 +102,30 @@ mod gui {
 
     impl Screen {
         pub fn run(&self) {
-            // run method will call draw on each trait object in components
+            // run is the entry point for the top level screen, the actual work is in draw
+            // so a screen nested in another one gets drawn the same way
+            self.draw();
+        }
+    }
+
+    impl Draw for Screen {
+        // a Screen is a component too, so it can go in another screen's components
+        fn draw(&self) {
+            // draw calls draw on each trait object in components, sub screens recurse from here
+            for component in self.components.iter() {
+                component.draw();
+            }
+        }
+    }
+
+    pub struct StaticScreen<T: Draw> {
+        // Generic version of Screen, every component has to be the same type T
+        // but calls to draw are dispatched statically (monomorphized) instead of through a vtable
+        pub components: Vec<T>,
+    }
+
+    impl<T: Draw> StaticScreen<T> {
+        pub fn run(&self) {
             for component in self.components.iter() {
                 component.draw();
             }
@@ -64,12 +137,326 @@ mod gui {
         pub width: u32,
         pub height: u32,
         pub label: String,
+        // draw only gets &self so a Cell is used to count draws
+        times_drawn: Cell<u32>,
+    }
+
+    impl Button {
+        pub fn new(width: u32, height: u32, label: String) -> Button {
+            Button {
+                width,
+                height,
+                label,
+                times_drawn: Cell::new(0),
+            }
+        }
+
+        pub fn times_drawn(&self) -> u32 {
+            self.times_drawn.get()
+        }
     }
 
     impl Draw for Button {
         // implements override of draw method from trait Draw
         fn draw(&self) {
             // code to actually draw a button
+            self.times_drawn.set(self.times_drawn.get() + 1);
+        }
+    }
+}
+
+mod observer {
+    pub trait Observer {
+        // anything that wants to hear about events implements notify
+        fn notify(&self, event: &str);
+    }
+
+    pub struct Subject {
+        // like Screen, a vector of trait objects so any mix of observer types can subscribe
+        observers: Vec<Box<dyn Observer>>,
+    }
+
+    impl Subject {
+        pub fn new() -> Subject {
+            Subject {
+                observers: Vec::new(),
+            }
+        }
+
+        pub fn subscribe(&mut self, observer: Box<dyn Observer>) {
+            self.observers.push(observer);
+        }
+
+        pub fn emit(&self, event: &str) {
+            // tell each observer in the order they subscribed
+            for observer in self.observers.iter() {
+                observer.notify(event);
+            }
+        }
+    }
+}
+
+mod blog {
+    // The state pattern with trait objects, the post holds its current state as a Box<dyn State>
+    // and each state decides what the next one is (oop_design does the same thing with types instead)
+    pub struct Post {
+        // Option so we can take() the old state out, the field can't be left empty while we swap
+        state: Option<Box<dyn State>>,
+        content: String,
+    }
+
+    impl Post {
+        pub fn new() -> Post {
+            Post {
+                state: Some(Box::new(Draft {})),
+                content: String::new(),
+            }
+        }
+
+        pub fn add_text(&mut self, text: &str) {
+            self.content.push_str(text);
+        }
+
+        pub fn content(&self) -> &str {
+            // the state decides whether the content is visible yet
+            self.state.as_ref().unwrap().content(self)
+        }
+
+        pub fn request_review(&mut self) {
+            if let Some(s) = self.state.take() {
+                self.state = Some(s.request_review())
+            }
+        }
+
+        pub fn approve(&mut self) {
+            if let Some(s) = self.state.take() {
+                self.state = Some(s.approve())
+            }
+        }
+    }
+
+    trait State {
+        // self: Box<Self> means the method takes ownership of the boxed state
+        // so the old state is used up when we transition
+        fn request_review(self: Box<Self>) -> Box<dyn State>;
+        fn approve(self: Box<Self>) -> Box<dyn State>;
+
+        // default is to show nothing, only Published overrides it
+        fn content<'a>(&self, _post: &'a Post) -> &'a str {
+            ""
+        }
+    }
+
+    struct Draft {}
+
+    impl State for Draft {
+        fn request_review(self: Box<Self>) -> Box<dyn State> {
+            Box::new(PendingReview {})
+        }
+
+        fn approve(self: Box<Self>) -> Box<dyn State> {
+            // can't approve something that hasn't been reviewed
+            self
+        }
+    }
+
+    struct PendingReview {}
+
+    impl State for PendingReview {
+        fn request_review(self: Box<Self>) -> Box<dyn State> {
+            self
+        }
+
+        fn approve(self: Box<Self>) -> Box<dyn State> {
+            Box::new(Published {})
+        }
+    }
+
+    struct Published {}
+
+    impl State for Published {
+        fn request_review(self: Box<Self>) -> Box<dyn State> {
+            self
+        }
+
+        fn approve(self: Box<Self>) -> Box<dyn State> {
+            self
+        }
+
+        fn content<'a>(&self, post: &'a Post) -> &'a str {
+            &post.content
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    struct MockObserver {
+        // notify only gets &self so the log needs interior mutability,
+        // and the Rc lets the test keep a handle after the Box is moved into the subject
+        received: Rc<RefCell<Vec<String>>>,
+    }
+
+    impl Observer for MockObserver {
+        fn notify(&self, event: &str) {
+            self.received.borrow_mut().push(String::from(event));
+        }
+    }
+
+    fn mock() -> (Box<MockObserver>, Rc<RefCell<Vec<String>>>) {
+        let received = Rc::new(RefCell::new(vec![]));
+        let observer = Box::new(MockObserver {
+            received: Rc::clone(&received),
+        });
+
+        (observer, received)
+    }
+
+    #[test]
+    fn observer_receives_events_in_order() {
+        let (observer, received) = mock();
+        let mut subject = Subject::new();
+        subject.subscribe(observer);
+
+        subject.emit("opened");
+        subject.emit("closed");
+
+        assert_eq!(*received.borrow(), vec!["opened", "closed"]);
+    }
+
+    #[test]
+    fn every_observer_is_notified() {
+        let (first, first_received) = mock();
+        let (second, second_received) = mock();
+        let mut subject = Subject::new();
+        subject.subscribe(first);
+        subject.subscribe(second);
+
+        subject.emit("clicked");
+
+        assert_eq!(*first_received.borrow(), vec!["clicked"]);
+        assert_eq!(*second_received.borrow(), vec!["clicked"]);
+    }
+
+    #[test]
+    fn events_before_subscribing_are_missed() {
+        let (observer, received) = mock();
+        let mut subject = Subject::new();
+
+        subject.emit("too early");
+        subject.subscribe(observer);
+        subject.emit("on time");
+
+        assert_eq!(*received.borrow(), vec!["on time"]);
+    }
+
+    #[test]
+    fn post_goes_draft_review_published() {
+        let mut post = Post::new();
+
+        post.add_text("I ate a salad for lunch today");
+        assert_eq!("", post.content());
+
+        post.request_review();
+        assert_eq!("", post.content());
+
+        post.approve();
+        assert_eq!("I ate a salad for lunch today", post.content());
+    }
+
+    #[test]
+    fn approving_a_draft_does_nothing() {
+        let mut post = Post::new();
+        post.add_text("not reviewed");
+
+        post.approve();
+        assert_eq!("", post.content());
+
+        // it still needs the review before approve counts
+        post.request_review();
+        post.approve();
+        assert_eq!("not reviewed", post.content());
+    }
+
+    #[test]
+    fn published_stays_published() {
+        let mut post = Post::new();
+        post.add_text("done");
+        post.request_review();
+        post.approve();
+
+        post.request_review();
+        post.approve();
+
+        assert_eq!("done", post.content());
+    }
+
+    fn buttons(labels: &[&str]) -> Vec<Button> {
+        labels
+            .iter()
+            .map(|label| Button::new(50, 10, String::from(*label)))
+            .collect()
+    }
+
+    #[test]
+    fn static_screen_draws_each_button() {
+        let screen = StaticScreen {
+            components: buttons(&["Save", "Cancel", "Help"]),
+        };
+
+        screen.run();
+
+        for button in screen.components.iter() {
+            assert_eq!(button.times_drawn(), 1, "{} not drawn once", button.label);
+        }
+    }
+
+    #[test]
+    fn static_screen_draws_again_each_run() {
+        let screen = StaticScreen {
+            components: buttons(&["OK"]),
+        };
+
+        screen.run();
+        screen.run();
+
+        assert_eq!(screen.components[0].times_drawn(), 2);
+    }
+
+    // lets the test keep hold of a button after a clone of it is boxed into a screen
+    impl<T: Draw> Draw for Rc<T> {
+        fn draw(&self) {
+            (**self).draw();
+        }
+    }
+
+    #[test]
+    fn nested_screen_draws_everything() {
+        let top_button = Rc::new(Button::new(50, 10, String::from("Back")));
+        let sub_buttons: Vec<Rc<Button>> =
+            buttons(&["Yes", "No"]).into_iter().map(Rc::new).collect();
+
+        let sub_screen = Screen {
+            components: sub_buttons
+                .iter()
+                .map(|b| Box::new(Rc::clone(b)) as Box<dyn Draw>)
+                .collect(),
+        };
+        let screen = Screen {
+            components: vec![Box::new(Rc::clone(&top_button)), Box::new(sub_screen)],
+        };
+
+        screen.run();
+
+        let total: u32 =
+            top_button.times_drawn() + sub_buttons.iter().map(|b| b.times_drawn()).sum::<u32>();
+        assert_eq!(total, 3);
+        // and each one exactly once
+        assert_eq!(top_button.times_drawn(), 1);
+        assert!(sub_buttons.iter().all(|b| b.times_drawn() == 1));
+    }
+}
@@ -1,34 +1,228 @@
-use std::{cmp::Ordering, io};
+use std::{cmp::Ordering, io, ops::RangeInclusive};
 use rand::Rng;
 
-fn main() {
-    println!("Guess The Number");
+// Each difficulty picks how big the range is and how many guesses you get
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl Difficulty {
+    fn range(&self) -> RangeInclusive<u32> {
+        match self {
+            Difficulty::Easy => 1..=10,
+            Difficulty::Medium => 1..=100,
+            Difficulty::Hard => 1..=1000,
+        }
+    }
+
+    fn max_attempts(&self) -> usize {
+        match self {
+            Difficulty::Easy => 5,
+            Difficulty::Medium => 7,
+            Difficulty::Hard => 10,
+        }
+    }
 
-    let secret_number = rand::thread_rng().gen_range(1..=100);
+    fn from_input(input: &str) -> Option<Difficulty> {
+        match input.trim().to_lowercase().as_str() {
+            "easy" | "e" => Some(Difficulty::Easy),
+            "medium" | "m" => Some(Difficulty::Medium),
+            "hard" | "h" => Some(Difficulty::Hard),
+            _ => None,
+        }
+    }
+}
 
-    loop {
-        println!("Please Input Your Guess");
+// How a round ended, each variant carries what is worth telling the player
+#[derive(Debug, PartialEq)]
+enum GameResult {
+    Won { attempts: usize },
+    Lost { secret: u32 },
+}
 
-        let mut guess: String = String::new();
+// The two ways a guess can be bad, kept separate so we can tell the player what went wrong
+#[derive(Debug, PartialEq)]
+enum GuessError {
+    NotANumber,
+    OutOfRange(RangeInclusive<u32>),
+}
 
-        io::stdin()
-            .read_line(&mut guess)
-            .expect("Failed to read line");
+// Pulled out of the loop so we can test it without typing into stdin
+fn parse_guess(input: &str, range: RangeInclusive<u32>) -> Result<u32, GuessError> {
+    let guess: u32 = input.trim().parse().map_err(|_| GuessError::NotANumber)?;
+
+    if !range.contains(&guess) {
+        return Err(GuessError::OutOfRange(range));
+    }
 
-        let guess: u32 = match guess.trim().parse() {
-            Ok(num) => num,
-            Err(_) => continue,
-        };
+    Ok(guess)
+}
 
+// The core game loop, it takes any iterator of guesses so a test can script them
+// instead of reading stdin. Running out of guesses early counts as a loss too.
+fn play_round(secret: u32, guesses: impl Iterator<Item = u32>, max_attempts: usize) -> GameResult {
+    for (attempt, guess) in guesses.take(max_attempts).enumerate() {
         println!("You guessed: {}", guess);
 
-        match guess.cmp(&secret_number) {
-            Ordering::Less => print!("Too Small"),
-            Ordering::Greater => print!("Too Large"),
+        match guess.cmp(&secret) {
+            Ordering::Less => println!("Too Small"),
+            Ordering::Greater => println!("Too Large"),
             Ordering::Equal => {
-                print!("You Win!");
-                break
-            },
+                // enumerate starts at 0 but the first guess is attempt 1
+                return GameResult::Won { attempts: attempt + 1 };
+            }
+        }
+    }
+
+    GameResult::Lost { secret }
+}
+
+fn main() {
+    println!("Guess The Number");
+
+    let difficulty = loop {
+        println!("Choose a difficulty (easy, medium, hard)");
+
+        let mut input = String::new();
+
+        io::stdin()
+            .read_line(&mut input)
+            .expect("Failed to read line");
+
+        match Difficulty::from_input(&input) {
+            Some(difficulty) => break difficulty,
+            None => println!("That isn't a difficulty"),
         }
+    };
+
+    let range = difficulty.range();
+    let secret_number = rand::thread_rng().gen_range(range.clone());
+
+    println!(
+        "Guess a number between {} and {}, you have {} attempts",
+        range.start(),
+        range.end(),
+        difficulty.max_attempts()
+    );
+
+    // Every line of stdin is turned into a guess, bad input is reported and skipped
+    // so it doesnt use up an attempt
+    let guesses = io::stdin()
+        .lines()
+        .map_while(Result::ok)
+        .filter_map(|line| match parse_guess(&line, range.clone()) {
+            Ok(num) => Some(num),
+            Err(GuessError::NotANumber) => {
+                println!("That isn't a number, try again");
+                None
+            }
+            Err(GuessError::OutOfRange(range)) => {
+                println!(
+                    "Your guess must be between {} and {}",
+                    range.start(),
+                    range.end()
+                );
+                None
+            }
+        });
+
+    match play_round(secret_number, guesses, difficulty.max_attempts()) {
+        GameResult::Won { attempts } => println!("You Win in {attempts} attempts!"),
+        GameResult::Lost { secret } => println!("Out of attempts, the number was {secret}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_letters() {
+        assert_eq!(parse_guess("abc", 1..=100), Err(GuessError::NotANumber));
+    }
+
+    #[test]
+    fn rejects_negative_as_not_a_number() {
+        // u32 cant hold a negative so this fails to parse at all
+        assert_eq!(parse_guess("-5", 1..=100), Err(GuessError::NotANumber));
+    }
+
+    #[test]
+    fn rejects_zero() {
+        assert_eq!(parse_guess("0", 1..=100), Err(GuessError::OutOfRange(1..=100)));
+    }
+
+    #[test]
+    fn rejects_above_100() {
+        assert_eq!(parse_guess("101", 1..=100), Err(GuessError::OutOfRange(1..=100)));
+    }
+
+    #[test]
+    fn accepts_valid_guess() {
+        assert_eq!(parse_guess("42", 1..=100), Ok(42));
+    }
+
+    #[test]
+    fn trims_whitespace_from_read_line() {
+        assert_eq!(parse_guess("  7\n", 1..=100), Ok(7));
+        assert_eq!(parse_guess("100\r\n", 1..=100), Ok(100));
+    }
+
+    #[test]
+    fn range_follows_difficulty() {
+        let easy = Difficulty::Easy.range();
+
+        assert_eq!(parse_guess("11", easy.clone()), Err(GuessError::OutOfRange(easy)));
+        assert_eq!(parse_guess("500", Difficulty::Hard.range()), Ok(500));
+    }
+
+    #[test]
+    fn parses_difficulty() {
+        assert_eq!(Difficulty::from_input("Easy\n"), Some(Difficulty::Easy));
+        assert_eq!(Difficulty::from_input("m"), Some(Difficulty::Medium));
+        assert_eq!(Difficulty::from_input(" HARD "), Some(Difficulty::Hard));
+        assert_eq!(Difficulty::from_input("impossible"), None);
+    }
+
+    #[test]
+    fn harder_difficulties_are_bigger() {
+        assert!(Difficulty::Easy.range().end() < Difficulty::Medium.range().end());
+        assert!(Difficulty::Medium.range().end() < Difficulty::Hard.range().end());
+    }
+
+    #[test]
+    fn wins_with_scripted_guesses() {
+        let guesses = vec![50, 25, 37, 42];
+
+        let result = play_round(42, guesses.into_iter(), 7);
+
+        assert_eq!(result, GameResult::Won { attempts: 4 });
+    }
+
+    #[test]
+    fn wins_on_last_attempt() {
+        let result = play_round(3, vec![1, 2, 3].into_iter(), 3);
+
+        assert_eq!(result, GameResult::Won { attempts: 3 });
+    }
+
+    #[test]
+    fn loses_when_attempts_run_out() {
+        // the right answer comes one guess too late
+        let guesses = vec![1, 2, 3, 4, 5, 6];
+
+        let result = play_round(6, guesses.into_iter(), 5);
+
+        assert_eq!(result, GameResult::Lost { secret: 6 });
+    }
+
+    #[test]
+    fn loses_when_guesses_run_out() {
+        let result = play_round(9, vec![1, 2].into_iter(), 5);
+
+        assert_eq!(result, GameResult::Lost { secret: 9 });
     }
 }
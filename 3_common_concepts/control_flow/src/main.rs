@@ -57,4 +57,104 @@ fn main() {
         println!("{number}!");
     }
     println!("LIFTOFF!!!");
+
+    /* loop returning a value is handy for calculations like fibonacci */
+    println!("The 10th fibonacci number is {}", nth_fibonacci(10));
+
+    /* the same sequence as an iterator, see FibIter below */
+    let first_ten: Vec<u64> = FibIter::new().take(10).collect();
+    println!("{first_ten:?}");
+}
+
+/* Uses loop + break value just like the counter example in main
+   fib(0) = 0, fib(1) = 1, and every number after is the sum of the two before it
+   fib(93) is the largest that fits in a u64, anything past that will overflow */
+fn nth_fibonacci(n: u32) -> u64 {
+    /* starting previous at 1 (think of it as fib(-1)) means we never calculate past fib(n) */
+    let mut previous: u64 = 1;
+    let mut current: u64 = 0;
+    let mut count = 0;
+
+    loop {
+        if count == n {
+            break current;
+        }
+
+        let sum = previous + current;
+        previous = current;
+        current = sum;
+        count += 1;
+    }
+}
+
+/* The same idea as an iterator, each call to next() moves the pair forward by one
+   This is covered properly in chapter 13 but it is a nice contrast to the loop above
+   The fields are Options so the iterator can end once the numbers no longer fit in a u64 */
+struct FibIter {
+    current: Option<u64>,
+    next: Option<u64>,
+}
+
+impl FibIter {
+    fn new() -> FibIter {
+        FibIter {
+            current: Some(0),
+            next: Some(1),
+        }
+    }
+}
+
+impl Iterator for FibIter {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let value = self.current?;
+
+        /* checked_add gives None when the sum would overflow, so that number is never produced */
+        let after_next = self.next.and_then(|next| value.checked_add(next));
+        self.current = self.next;
+        self.next = after_next;
+
+        Some(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_fibonacci_numbers() {
+        let expected = [0, 1, 1, 2, 3, 5, 8, 13, 21, 34];
+
+        for (n, value) in expected.iter().enumerate() {
+            assert_eq!(nth_fibonacci(n as u32), *value);
+        }
+    }
+
+    #[test]
+    fn larger_fibonacci_number() {
+        assert_eq!(nth_fibonacci(50), 12_586_269_025);
+    }
+
+    #[test]
+    fn iterator_produces_sequence() {
+        let first_ten: Vec<u64> = FibIter::new().take(10).collect();
+
+        assert_eq!(first_ten, vec![0, 1, 1, 2, 3, 5, 8, 13, 21, 34]);
+    }
+
+    #[test]
+    fn iterator_matches_loop() {
+        for (n, value) in FibIter::new().take(60).enumerate() {
+            assert_eq!(nth_fibonacci(n as u32), value);
+        }
+    }
+
+    #[test]
+    fn iterator_stops_before_overflow() {
+        /* fib(0) through fib(93) all fit in a u64, fib(94) does not */
+        assert_eq!(FibIter::new().count(), 94);
+        assert_eq!(FibIter::new().last(), Some(nth_fibonacci(93)));
+    }
 }
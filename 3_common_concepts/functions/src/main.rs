@@ -1,3 +1,7 @@
+mod temperature;
+
+use temperature::Temperature;
+
 fn main() {
     println!("Hello, world!");
 
@@ -13,6 +17,15 @@ fn main() {
 
     /* call a function as an expression */
     let five: i32 = five();
+
+    /* functions, enums and methods working together (see temperature.rs) */
+    let boiling = temperature::c_to_f(100.0);
+    println!("100C is {boiling}F");
+
+    let temps = [Temperature::Fahrenheit(98.6), Temperature::Celsius(21.0)];
+    for temp in temps {
+        println!("{temp:?} is {:.1}C", temp.to_celsius());
+    }
 }
 
 fn another_function(value: u32, unit_label: char) {
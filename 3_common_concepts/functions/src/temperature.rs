@@ -0,0 +1,66 @@
+/* Converting between the two scales is just a formula, a nice fit for small functions */
+pub fn c_to_f(c: f64) -> f64 {
+    c * 9.0 / 5.0 + 32.0 /* no ';' so this is the return value */
+}
+
+pub fn f_to_c(f: f64) -> f64 {
+    (f - 32.0) * 5.0 / 9.0
+}
+
+/* An enum lets a value carry which scale it is in along with the number */
+#[derive(Debug, PartialEq)]
+pub enum Temperature {
+    Celsius(f64),
+    Fahrenheit(f64),
+}
+
+impl Temperature {
+    /* The method reuses the plain functions above */
+    pub fn to_celsius(&self) -> f64 {
+        match self {
+            Temperature::Celsius(c) => *c,
+            Temperature::Fahrenheit(f) => f_to_c(*f),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /* floating point math isnt exact so compare with a small tolerance */
+    fn approx_eq(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn boiling_point() {
+        assert!(approx_eq(c_to_f(100.0), 212.0));
+        assert!(approx_eq(f_to_c(212.0), 100.0));
+    }
+
+    #[test]
+    fn freezing_point() {
+        assert!(approx_eq(c_to_f(0.0), 32.0));
+        assert!(approx_eq(f_to_c(32.0), 0.0));
+    }
+
+    #[test]
+    fn scales_cross_at_minus_40() {
+        assert!(approx_eq(c_to_f(-40.0), -40.0));
+        assert!(approx_eq(f_to_c(-40.0), -40.0));
+    }
+
+    #[test]
+    fn round_trip_is_stable() {
+        for c in [-273.15, -40.0, 0.0, 36.6, 100.0, 1234.5] {
+            assert!(approx_eq(f_to_c(c_to_f(c)), c));
+        }
+    }
+
+    #[test]
+    fn enum_to_celsius() {
+        assert!(approx_eq(Temperature::Celsius(21.5).to_celsius(), 21.5));
+        assert!(approx_eq(Temperature::Fahrenheit(212.0).to_celsius(), 100.0));
+    }
+}
@@ -5,6 +5,43 @@
     -When the owner goes out of scope, the value will be dropped.
 */
 
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/* A String wrapper that counts how many times its heap data has been deep copied.
+   The counter lives behind an Arc so every copy of a TrackedString shares the same count,
+   rather than one truly global static, so separate tests dont bump each others counters.
+*/
+struct TrackedString {
+    value: String,
+    clones: Arc<AtomicUsize>,
+}
+
+impl TrackedString {
+    fn new(value: &str) -> TrackedString {
+        TrackedString {
+            value: String::from(value),
+            clones: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    fn clone_count(&self) -> usize {
+        self.clones.load(Ordering::SeqCst)
+    }
+}
+
+/* Our own Clone so we can count, a move never calls this, only .clone() does */
+impl Clone for TrackedString {
+    fn clone(&self) -> TrackedString {
+        self.clones.fetch_add(1, Ordering::SeqCst);
+
+        TrackedString {
+            value: self.value.clone(), /* this is the real deep copy of the heap data */
+            clones: Arc::clone(&self.clones),
+        }
+    }
+}
+
 fn main() {
     /* Scope */
     {
@@ -43,6 +80,19 @@ fn main() {
     let s1 = String::from("hello");
     let s2 = s1;
 
+    /* We can actually watch this happen with TrackedString, the move copies nothing
+       but .clone() makes a whole new heap allocation
+    */
+    let t1 = TrackedString::new("hello");
+    let t2 = t1; /* move */
+    let t3 = t2.clone(); /* deep copy */
+    println!(
+        "{} and {} after {} clone(s)",
+        t2.value,
+        t3.value,
+        t3.clone_count()
+    );
+
     /* In this case the old allocated heap address for 'hello' is immediately dropped
        We then go ahead and create and allocate the new space for 'ahoy'
     */
@@ -81,3 +131,46 @@ fn calculate_length(s: String) -> (String, usize) {
 
     (s, length)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn move_does_not_copy() {
+        let s1 = TrackedString::new("hello");
+        let before = s1.clone_count();
+
+        let s2 = s1;
+        let s3 = s2;
+
+        assert_eq!(s3.clone_count() - before, 0);
+    }
+
+    #[test]
+    fn clone_copies_each_time() {
+        let s1 = TrackedString::new("hello");
+        let before = s1.clone_count();
+
+        let s2 = s1.clone();
+        let s3 = s2.clone();
+
+        assert_eq!(s1.clone_count() - before, 2);
+        /* every copy shares the same counter */
+        assert_eq!(s3.clone_count(), s1.clone_count());
+        assert_eq!(s3.value, "hello");
+    }
+
+    #[test]
+    fn passing_to_a_function_is_a_move() {
+        fn take(s: TrackedString) -> TrackedString {
+            s
+        }
+
+        let s1 = TrackedString::new("hello");
+
+        let s2 = take(s1);
+
+        assert_eq!(s2.clone_count(), 0);
+    }
+}
@@ -36,6 +36,16 @@ fn main() {
     let slice = &a[1..3];
 
     assert_eq!(slice, &[2, 3]);
+
+    /* Slicing every word out of a string, none of these words are copies */
+    let sentence = "  the quick   brown fox ";
+    println!("{:?}", words(sentence));
+    println!("{:?}", nth_word(sentence, 2));
+
+    /* The same idea works with several delimiters, here for sentences */
+    let paragraph = "Slices are great. Are they? They are!";
+    println!("{}", first_sentence(paragraph));
+    println!("{:?}", sentences(paragraph));
 }
 
 /* Lets try to return a subset of a string (ie the first word) without using a slice */
@@ -63,3 +73,156 @@ fn new_first_word(s: &String) -> &str {
     /* Returns the entire thing as a slice */
     &s[..]
 }
+
+/* Taking &str instead of &String lets these work on both String and string literals */
+/* Every returned &str is a slice pointing back into s, so s must outlive them */
+/* split_whitespace treats any run of whitespace as one gap and skips it at the ends, so no empty words */
+fn words(s: &str) -> Vec<&str> {
+    s.split_whitespace().collect()
+}
+
+fn nth_word(s: &str, n: usize) -> Option<&str> {
+    s.split_whitespace().nth(n)
+}
+
+fn is_sentence_end(c: char) -> bool {
+    c == '.' || c == '!' || c == '?'
+}
+
+/* Like first_word but stops at the end of a sentence instead of a space */
+/* The punctuation is kept, and a run of it like "?!" or "..." stays together */
+/* If there is no punctuation at all the whole string is one sentence */
+fn first_sentence(s: &str) -> &str {
+    let Some(start) = s.find(is_sentence_end) else {
+        return s;
+    };
+
+    /* walk past any more punctuation right after the first one */
+    let end = s[start..]
+        .find(|c: char| !is_sentence_end(c))
+        .map_or(s.len(), |offset| start + offset);
+
+    &s[..end]
+}
+
+/* Keeps taking the first sentence off the front until nothing is left */
+/* The whitespace between sentences is trimmed so each slice starts at its first word */
+fn sentences(s: &str) -> Vec<&str> {
+    let mut result = Vec::new();
+    let mut rest = s.trim_start();
+
+    while !rest.is_empty() {
+        let sentence = first_sentence(rest);
+        result.push(sentence);
+        rest = rest[sentence.len()..].trim_start();
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn words_simple() {
+        assert_eq!(words("hello world"), vec!["hello", "world"]);
+    }
+
+    #[test]
+    fn words_multiple_spaces() {
+        assert_eq!(words("hello    big\tworld"), vec!["hello", "big", "world"]);
+    }
+
+    #[test]
+    fn words_leading_and_trailing_whitespace() {
+        assert_eq!(words("   hello world  \n"), vec!["hello", "world"]);
+    }
+
+    #[test]
+    fn words_empty() {
+        assert!(words("").is_empty());
+        assert!(words("    ").is_empty());
+    }
+
+    #[test]
+    fn words_borrow_from_input() {
+        let s = String::from("hello world");
+
+        let found = words(&s);
+
+        /* the slice points into the same memory as s, it wasnt copied */
+        assert_eq!(found[1].as_ptr(), s[6..].as_ptr());
+    }
+
+    #[test]
+    fn nth_word_in_range() {
+        let s = "  the quick   brown fox ";
+
+        assert_eq!(nth_word(s, 0), Some("the"));
+        assert_eq!(nth_word(s, 3), Some("fox"));
+    }
+
+    #[test]
+    fn nth_word_out_of_range() {
+        assert_eq!(nth_word("the quick brown fox", 4), None);
+        assert_eq!(nth_word("", 0), None);
+    }
+
+    const PARAGRAPH: &str = "Rust is fast. Is it safe? Yes! It is productive too.";
+
+    #[test]
+    fn first_sentence_of_paragraph() {
+        assert_eq!(first_sentence(PARAGRAPH), "Rust is fast.");
+    }
+
+    #[test]
+    fn first_sentence_each_delimiter() {
+        assert_eq!(first_sentence("Is it safe? Yes."), "Is it safe?");
+        assert_eq!(first_sentence("Yes! It is."), "Yes!");
+    }
+
+    #[test]
+    fn first_sentence_keeps_repeated_punctuation() {
+        assert_eq!(first_sentence("Really?! No way."), "Really?!");
+        assert_eq!(first_sentence("Well... ok."), "Well...");
+    }
+
+    #[test]
+    fn first_sentence_without_punctuation_is_everything() {
+        assert_eq!(first_sentence("no punctuation here"), "no punctuation here");
+        assert_eq!(first_sentence(""), "");
+    }
+
+    #[test]
+    fn sentences_of_paragraph() {
+        assert_eq!(
+            sentences(PARAGRAPH),
+            vec![
+                "Rust is fast.",
+                "Is it safe?",
+                "Yes!",
+                "It is productive too."
+            ]
+        );
+    }
+
+    #[test]
+    fn sentences_keeps_unterminated_last_sentence() {
+        assert_eq!(
+            sentences("First one.  And then\nthe rest"),
+            vec!["First one.", "And then\nthe rest"]
+        );
+    }
+
+    #[test]
+    fn sentences_without_punctuation_is_whole_string() {
+        assert_eq!(sentences("just one thought"), vec!["just one thought"]);
+    }
+
+    #[test]
+    fn sentences_of_blank_input() {
+        assert!(sentences("").is_empty());
+        assert!(sentences("   ").is_empty());
+    }
+}
@@ -1,68 +1,267 @@
-struct User {
-    is_active: bool,
-    username: String,
-    password: String,
-    login_count: u64,
+// User lives in its own module so that the password field can actually be private
+// (everything in the same module can see private fields, so without this it would be pointless)
+mod user {
+    use std::collections::hash_map::RandomState;
+    use std::hash::{BuildHasher, DefaultHasher, Hash, Hasher};
+
+    const MIN_PASSWORD_LEN: usize = 8;
+
+    pub struct User {
+        pub is_active: bool,
+        pub username: String,
+        // we never store the raw password, only a salted hash of it
+        // note: DefaultHasher is NOT a real password hash, use something like argon2 for that
+        password_hash: u64,
+        salt: u64,
+        pub login_count: u64,
+    }
+
+    impl User {
+        // the only way to make a User from outside this module, so every user has a valid password
+        pub fn new(username: String, password: &str) -> Result<User, String> {
+            let mut user = User {
+                is_active: true,
+                username,
+                password_hash: 0,
+                salt: 0,
+                login_count: 0,
+            };
+
+            user.set_password(password)?;
+
+            Ok(user)
+        }
+
+        // checks the password is long enough and then stores a freshly salted hash of it
+        pub fn set_password(&mut self, raw: &str) -> Result<(), String> {
+            if raw.chars().count() < MIN_PASSWORD_LEN {
+                return Err(format!(
+                    "password must be at least {MIN_PASSWORD_LEN} characters"
+                ));
+            }
+
+            // RandomState is seeded randomly so each call gets a different salt
+            self.salt = RandomState::new().hash_one(&self.username);
+            self.password_hash = hash_password(self.salt, raw);
+
+            Ok(())
+        }
+
+        // hashes the attempt with the same salt and compares
+        pub fn verify_password(&self, attempt: &str) -> bool {
+            hash_password(self.salt, attempt) == self.password_hash
+        }
+
+        // Can copy attributes from another user using this cool .. shorthand
+        // Does not appear to take ownership looks like it does a copy
+        // (this has to live in the module now since the shorthand needs to see every field)
+        pub fn with_username(self, username: String) -> User {
+            User { username, ..self }
+        }
+    }
+
+    fn hash_password(salt: u64, raw: &str) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        salt.hash(&mut hasher);
+        raw.hash(&mut hasher);
+        hasher.finish()
+    }
 }
 
+use std::fmt;
+use user::User;
+
 // Tuple Struct
+#[derive(Debug, PartialEq)]
 struct Color(i32, i32, i32);
 struct Point(i32, i32, i32);
 
+impl Color {
+    // mixes two colors by averaging each channel
+    fn blend(&self, other: &Color) -> Color {
+        Color(
+            (self.0 + other.0) / 2,
+            (self.1 + other.1) / 2,
+            (self.2 + other.2) / 2,
+        )
+    }
+
+    // parses "#RRGGBB" (the # is optional) back into a Color
+    fn from_hex(s: &str) -> Result<Color, String> {
+        let hex = s.strip_prefix('#').unwrap_or(s);
+
+        // checking every char is a hex digit up front means the byte slicing below
+        // can never split a char, and stops from_str_radix accepting a sign like "+1"
+        if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(format!("expected 6 hex digits, got {s:?}"));
+        }
+
+        let channel = |range: std::ops::Range<usize>| {
+            i32::from_str_radix(&hex[range], 16).map_err(|e| format!("invalid hex {s:?}: {e}"))
+        };
+
+        Ok(Color(channel(0..2)?, channel(2..4)?, channel(4..6)?))
+    }
+}
+
+// lets us print a Color with {} as a hex code like #FF8000
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // the fields are i32 so anything outside a byte gets clamped first
+        let clamp = |c: i32| c.clamp(0, 255);
+
+        write!(
+            f,
+            "#{:02X}{:02X}{:02X}",
+            clamp(self.0),
+            clamp(self.1),
+            clamp(self.2)
+        )
+    }
+}
+
 // Unit Struct
 struct AlwaysEqual;
 
 fn main() {
     print!("Structs!");
 
-    let mut user_one = User {
-        is_active: true,
-        username: String::from("Default User"),
-        password: String::from("Default Password"),
-        login_count: 0,
-    };
+    let mut user_one = User::new(String::from("Default User"), "Default Password").unwrap();
 
     user_one.login_count = 1;
 
-    let new_user = create_user(true, String::from("MyUser"), String::from("My Password"), 1);
-
-    // Can copy attributes from another user using this cool .. shorthand
-    // Does not appear to take ownership looks like it does a copy
-    let user2 = User {
-        username: String::from("another_name"),
-        ..new_user
-    };
+    let new_user = create_user(true, String::from("MyUser"), "My Password", 1).unwrap();
 
     print!("{}", new_user.login_count);
 
+    let user2 = new_user.with_username(String::from("another_name"));
+
+    // passwords can only be checked, never read back
+    println!("{}", user2.verify_password("My Password"));
+
+    // and a short password is rejected
+    if let Err(e) = user_one.set_password("short") {
+        println!("{e}");
+    }
+
     // Init Tuple Struct
     let black = Color(0, 0, 0);
+    let white = Color::from_hex("#FFFFFF").unwrap();
+
+    // Display gives us the hex code back
+    println!("{black} + {white} = {}", black.blend(&white));
+
     let origin = Point(0, 0, 0);
 
     let subject = AlwaysEqual;
 }
 
 // Function to create and return a user
+// creating one can fail now since the password might be rejected
 fn create_user(
     new_active: bool,
     new_user: String,
-    new_password: String,
+    new_password: &str,
     new_login_count: u64,
-) -> User {
-    User {
-        is_active: new_active,
-        username: new_user,
-        password: new_password,
-        login_count: new_login_count,
-    }
+) -> Result<User, String> {
+    let mut user = User::new(new_user, new_password)?;
+    user.is_active = new_active;
+    user.login_count = new_login_count;
+
+    Ok(user)
 }
 
 // Short hand creation method
-fn create_user_two(password: String, username: String) -> User {
-    User {
-        is_active: true,
-        username,
-        password,
-        login_count: 1,
+fn create_user_two(password: &str, username: String) -> Result<User, String> {
+    let mut user = User::new(username, password)?;
+    user.login_count = 1;
+
+    Ok(user)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn displays_as_hex() {
+        assert_eq!(Color(255, 128, 0).to_string(), "#FF8000");
+        assert_eq!(Color(0, 0, 0).to_string(), "#000000");
+    }
+
+    #[test]
+    fn display_clamps_channels() {
+        assert_eq!(Color(300, -20, 15).to_string(), "#FF000F");
+    }
+
+    #[test]
+    fn hex_round_trip() {
+        let color = Color(18, 52, 171);
+
+        assert_eq!(Color::from_hex(&color.to_string()), Ok(color));
+        assert_eq!(Color::from_hex("a1b2c3").unwrap().to_string(), "#A1B2C3");
+    }
+
+    #[test]
+    fn from_hex_rejects_bad_input() {
+        assert!(Color::from_hex("#FFF").is_err());
+        assert!(Color::from_hex("#GGGGGG").is_err());
+        assert!(Color::from_hex("#FFFFFFF").is_err());
+        assert!(Color::from_hex("#ééé").is_err());
+        assert!(Color::from_hex("#+1+1+1").is_err());
+    }
+
+    #[test]
+    fn blends_black_and_white_to_gray() {
+        let black = Color(0, 0, 0);
+        let white = Color(255, 255, 255);
+
+        let gray = black.blend(&white);
+
+        assert_eq!(gray, Color(127, 127, 127));
+        assert_eq!(gray.to_string(), "#7F7F7F");
+    }
+
+    #[test]
+    fn rejects_short_password() {
+        let result = User::new(String::from("MyUser"), "short");
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn set_password_rejects_short_and_keeps_old() {
+        let mut user = User::new(String::from("MyUser"), "My Password").unwrap();
+
+        assert!(user.set_password("tiny").is_err());
+        // the old password still works since nothing was changed
+        assert!(user.verify_password("My Password"));
+    }
+
+    #[test]
+    fn verifies_correct_password() {
+        let user = User::new(String::from("MyUser"), "My Password").unwrap();
+
+        assert!(user.verify_password("My Password"));
+        assert!(!user.verify_password("my password"));
+    }
+
+    #[test]
+    fn set_password_replaces_old() {
+        let mut user = User::new(String::from("MyUser"), "My Password").unwrap();
+
+        user.set_password("A Better Password").unwrap();
+
+        assert!(user.verify_password("A Better Password"));
+        assert!(!user.verify_password("My Password"));
+    }
+
+    #[test]
+    fn password_survives_username_change() {
+        let user = create_user_two("My Password", String::from("MyUser")).unwrap();
+        let renamed = user.with_username(String::from("another_name"));
+
+        assert_eq!(renamed.username, "another_name");
+        assert!(renamed.verify_password("My Password"));
     }
 }
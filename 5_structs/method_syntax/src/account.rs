@@ -0,0 +1,249 @@
+// A bank account, balance is kept private so the only way to change it is through
+// the methods below, which means it can never go negative
+// Amounts are in cents so we dont have to deal with floating point money
+#[derive(Debug)]
+pub struct Account {
+    balance: i64,
+}
+
+impl Account {
+    pub fn new() -> Self {
+        Self { balance: 0 }
+    }
+
+    // &self since reading the balance doesnt change anything
+    pub fn balance(&self) -> i64 {
+        self.balance
+    }
+
+    // &mut self since we are changing the balance
+    // a negative deposit would really be a withdrawal that skips the overdraft check, so it is rejected,
+    // checked_add gives None instead of overflowing (which would panic in a debug build)
+    pub fn deposit(&mut self, amount: i64) -> Result<(), String> {
+        check_amount(amount)?;
+
+        self.balance = self
+            .balance
+            .checked_add(amount)
+            .ok_or_else(|| format!("depositing {amount} would overflow the balance"))?;
+        Ok(())
+    }
+
+    // Overdrawing isnt a bug in our program, its something that just happens,
+    // so we return a Result rather than panicking and let the caller decide what to do
+    pub fn withdraw(&mut self, amount: i64) -> Result<(), String> {
+        check_amount(amount)?;
+
+        if amount > self.balance {
+            return Err(format!(
+                "insufficient funds: tried to withdraw {amount} with a balance of {}",
+                self.balance
+            ));
+        }
+
+        self.balance -= amount;
+        Ok(())
+    }
+}
+
+// Every amount moved in or out of an account has to be positive
+fn check_amount(amount: i64) -> Result<(), String> {
+    if amount <= 0 {
+        return Err(format!("amount must be positive, got {amount}"));
+    }
+
+    Ok(())
+}
+
+// Moves money from one account to another.
+// We can hold two &mut at once here because they point at two different accounts,
+// the borrow checker only stops two mutable references to the same value
+// (transfer(&mut a, &mut a, 10) wont compile).
+// The amount is checked before anything else, then the withdraw happens so if it fails
+// we return before touching `to`. If the deposit then fails (`to` would overflow) the money
+// goes back into `from`, that way either both balances change or neither does
+pub fn transfer(from: &mut Account, to: &mut Account, amount: i64) -> Result<(), String> {
+    check_amount(amount)?;
+    from.withdraw(amount)?;
+
+    if let Err(e) = to.deposit(amount) {
+        // from held this amount a moment ago so putting it back cant fail
+        from.balance += amount;
+        return Err(e);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_account_is_empty() {
+        assert_eq!(Account::new().balance(), 0);
+    }
+
+    #[test]
+    fn withdraw_with_enough_funds() {
+        let mut account = Account::new();
+        account.deposit(1000).unwrap();
+
+        assert_eq!(account.withdraw(400), Ok(()));
+        assert_eq!(account.balance(), 600);
+    }
+
+    #[test]
+    fn withdraw_entire_balance() {
+        let mut account = Account::new();
+        account.deposit(1000).unwrap();
+
+        assert!(account.withdraw(1000).is_ok());
+        assert_eq!(account.balance(), 0);
+    }
+
+    #[test]
+    fn overdraft_is_an_error() {
+        let mut account = Account::new();
+        account.deposit(500).unwrap();
+
+        let result = account.withdraw(501);
+
+        assert_eq!(
+            result,
+            Err(String::from(
+                "insufficient funds: tried to withdraw 501 with a balance of 500"
+            ))
+        );
+        // a failed withdrawal leaves the balance alone
+        assert_eq!(account.balance(), 500);
+    }
+
+    #[test]
+    fn balance_after_a_sequence() {
+        let mut account = Account::new();
+
+        account.deposit(2500).unwrap();
+        account.withdraw(1000).unwrap();
+        account.deposit(300).unwrap();
+        assert!(account.withdraw(5000).is_err());
+        account.withdraw(800).unwrap();
+
+        assert_eq!(account.balance(), 1000);
+    }
+
+    #[test]
+    fn deposit_rejects_negative_and_zero() {
+        let mut account = Account::new();
+        account.deposit(100).unwrap();
+
+        assert_eq!(
+            account.deposit(-100),
+            Err(String::from("amount must be positive, got -100"))
+        );
+        assert!(account.deposit(0).is_err());
+        assert_eq!(account.balance(), 100);
+    }
+
+    #[test]
+    fn withdraw_rejects_negative_and_zero() {
+        let mut account = Account::new();
+        account.deposit(100).unwrap();
+
+        assert_eq!(
+            account.withdraw(-100),
+            Err(String::from("amount must be positive, got -100"))
+        );
+        assert!(account.withdraw(0).is_err());
+        assert_eq!(account.balance(), 100);
+    }
+
+    #[test]
+    fn deposit_that_would_overflow_is_an_error() {
+        let mut account = Account::new();
+        account.deposit(i64::MAX).unwrap();
+
+        assert!(account.deposit(1).is_err());
+        assert_eq!(account.balance(), i64::MAX);
+    }
+
+    #[test]
+    fn transfer_moves_funds() {
+        let mut alice = Account::new();
+        let mut bob = Account::new();
+        alice.deposit(1000).unwrap();
+        bob.deposit(200).unwrap();
+
+        assert!(transfer(&mut alice, &mut bob, 300).is_ok());
+
+        assert_eq!(alice.balance(), 700);
+        assert_eq!(bob.balance(), 500);
+    }
+
+    #[test]
+    fn failed_transfer_changes_nothing() {
+        let mut alice = Account::new();
+        let mut bob = Account::new();
+        alice.deposit(100).unwrap();
+        bob.deposit(200).unwrap();
+
+        assert!(transfer(&mut alice, &mut bob, 150).is_err());
+
+        assert_eq!(alice.balance(), 100);
+        assert_eq!(bob.balance(), 200);
+    }
+
+    #[test]
+    fn negative_transfer_changes_nothing() {
+        let mut alice = Account::new();
+        let mut bob = Account::new();
+        alice.deposit(100).unwrap();
+        bob.deposit(200).unwrap();
+
+        assert_eq!(
+            transfer(&mut alice, &mut bob, -500),
+            Err(String::from("amount must be positive, got -500"))
+        );
+
+        assert_eq!(alice.balance(), 100);
+        assert_eq!(bob.balance(), 200);
+    }
+
+    #[test]
+    fn zero_transfer_changes_nothing() {
+        let mut alice = Account::new();
+        let mut bob = Account::new();
+        alice.deposit(100).unwrap();
+
+        assert!(transfer(&mut alice, &mut bob, 0).is_err());
+
+        assert_eq!(alice.balance(), 100);
+        assert_eq!(bob.balance(), 0);
+    }
+
+    #[test]
+    fn transfer_that_would_overflow_changes_nothing() {
+        let mut alice = Account::new();
+        let mut bob = Account::new();
+        alice.deposit(100).unwrap();
+        bob.deposit(i64::MAX).unwrap();
+
+        assert!(transfer(&mut alice, &mut bob, 50).is_err());
+
+        assert_eq!(alice.balance(), 100);
+        assert_eq!(bob.balance(), i64::MAX);
+    }
+
+    #[test]
+    fn transfer_back_and_forth() {
+        let mut alice = Account::new();
+        let mut bob = Account::new();
+        alice.deposit(1000).unwrap();
+
+        transfer(&mut alice, &mut bob, 600).unwrap();
+        transfer(&mut bob, &mut alice, 100).unwrap();
+
+        assert_eq!(alice.balance(), 500);
+        assert_eq!(bob.balance(), 500);
+    }
+}
@@ -1,3 +1,7 @@
+mod account;
+
+use account::{transfer, Account};
+
 #[derive(Debug)]
 struct Rectangle {
     width: u32,
@@ -59,4 +63,25 @@ fn main() {
 
     // Can use an associated function to create a square of a rectangle like this
     let _square = Rectangle::square(2);
+
+    // Methods really shine when a struct has rules to protect, see account.rs
+    let mut account = Account::new();
+    account.deposit(5000).unwrap();
+
+    match account.withdraw(7500) {
+        Ok(()) => println!("Withdrew 7500 cents"),
+        Err(e) => println!("Couldn't withdraw: {e}"),
+    }
+
+    println!("Balance is {} cents", account.balance());
+
+    // two &mut borrows at once are fine as long as they are of different accounts
+    let mut savings = Account::new();
+    if transfer(&mut account, &mut savings, 2000).is_ok() {
+        println!(
+            "Checking: {} cents, Savings: {} cents",
+            account.balance(),
+            savings.balance()
+        );
+    }
 }
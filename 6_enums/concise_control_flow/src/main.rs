@@ -7,11 +7,17 @@ enum Coin {
     Quarter(UsState),
 }
 
-#[derive(Debug)] // so we can inspect the state in a minute
+#[derive(Debug, PartialEq)] // so we can inspect the state in a minute
 enum UsState {
     Alabama,
     Alaska,
-    // --snip--
+    Arizona,
+    California,
+    Delaware,
+    Hawaii,
+    Oklahoma,
+    Texas,
+    Utah,
 }
 
 fn main() {
@@ -47,6 +53,13 @@ fn main() {
         count += 1;
     }
 
+    // if let also works nicely inside a loop to pick out only the variants we want
+    let coins = vec![
+        Coin::Quarter(UsState::Delaware),
+        Coin::Penny,
+        Coin::Quarter(UsState::Hawaii),
+    ];
+    println!("Around in 1900: {:?}", quarters_available_in(&coins, 1900));
 }
 
 impl UsState {
@@ -54,11 +67,33 @@ impl UsState {
         match self {
             UsState::Alabama => year >= 1819,
             UsState::Alaska => year >= 1959,
-            // -- snip --
+            UsState::Arizona => year >= 1912,
+            UsState::California => year >= 1850,
+            UsState::Delaware => year >= 1787,
+            UsState::Hawaii => year >= 1959,
+            UsState::Oklahoma => year >= 1907,
+            UsState::Texas => year >= 1845,
+            UsState::Utah => year >= 1896,
         }
     }
 }
 
+// Goes through a pile of coins and picks out the states that were around in the given year
+// We borrow the coins so the Vec holds references into the slice rather than copies
+fn quarters_available_in(coins: &[Coin], year: u16) -> Vec<&UsState> {
+    let mut states = Vec::new();
+
+    for coin in coins {
+        if let Coin::Quarter(state) = coin {
+            if state.existed_in(year) {
+                states.push(state);
+            }
+        }
+    }
+
+    states
+}
+
 fn describe_state_quarter(coin: Coin) -> Option<String> {
     if let Coin::Quarter(state) = coin {
         if state.existed_in(1900) {
@@ -99,4 +134,71 @@ fn describe_state_quarter_three(coin: Coin) -> Option<String> {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn coins() -> Vec<Coin> {
+        vec![
+            Coin::Quarter(UsState::Alabama),
+            Coin::Penny,
+            Coin::Quarter(UsState::Alaska),
+            Coin::Quarter(UsState::Arizona),
+            Coin::Dime,
+            Coin::Quarter(UsState::California),
+            Coin::Quarter(UsState::Delaware),
+            Coin::Nickel,
+            Coin::Quarter(UsState::Hawaii),
+            Coin::Quarter(UsState::Oklahoma),
+            Coin::Quarter(UsState::Texas),
+            Coin::Quarter(UsState::Utah),
+        ]
+    }
+
+    #[test]
+    fn filters_quarters_by_1900() {
+        let coins = coins();
+
+        assert_eq!(
+            quarters_available_in(&coins, 1900),
+            vec![
+                &UsState::Alabama,
+                &UsState::California,
+                &UsState::Delaware,
+                &UsState::Texas,
+                &UsState::Utah,
+            ]
+        );
+    }
+
+    #[test]
+    fn statehood_year_is_inclusive() {
+        let coins = coins();
 
+        let states = quarters_available_in(&coins, 1912);
+
+        assert!(states.contains(&&UsState::Arizona));
+        assert!(!states.contains(&&UsState::Hawaii));
+    }
+
+    #[test]
+    fn every_state_exists_today() {
+        let coins = coins();
+
+        assert_eq!(quarters_available_in(&coins, 2024).len(), 9);
+    }
+
+    #[test]
+    fn no_states_before_the_first() {
+        let coins = coins();
+
+        assert!(quarters_available_in(&coins, 1700).is_empty());
+    }
+
+    #[test]
+    fn ignores_non_quarters() {
+        let coins = vec![Coin::Penny, Coin::Nickel, Coin::Dime];
+
+        assert!(quarters_available_in(&coins, 2024).is_empty());
+    }
+}
@@ -1,9 +1,36 @@
+use std::collections::HashMap;
+use std::ops::Add;
+
 fn main() {
     println!("Hello, world!");
 
     let five = Some(5);
     let six = plus_one(five);
-    let none = plus_one(None);
+    // since plus_one is generic a bare None needs to be told what it would hold
+    let none: Option<i32> = plus_one(None);
+
+    // Lets see what coins make up 99 cents
+    let change = make_change(99);
+    println!("{change:?}");
+
+    for n in [-4, 0, 7, 40, 42, 1000] {
+        println!("{n} is {}", categorize(n));
+    }
+
+    for p in [
+        Point { x: 0, y: 0 },
+        Point { x: 3, y: 0 },
+        Point { x: 0, y: -2 },
+        Point { x: 1, y: 7 },
+    ] {
+        println!("{p:?} is {}", describe_point(p));
+    }
+
+    // -(2 + 3 * 4), the multiplication is nested inside the addition
+    let product = Expr::Mul(Box::new(Expr::Num(3.0)), Box::new(Expr::Num(4.0)));
+    let sum = Expr::Add(Box::new(Expr::Num(2.0)), Box::new(product));
+    let expr = Expr::Neg(Box::new(sum));
+    println!("{expr:?} = {}", eval(&expr));
 
     let dice_roll = 9;
 
@@ -44,6 +71,51 @@ fn value_in_cents(coin: Coin) -> u8 {
     }
 }
 
+// Quarter carries a UsState so it cant be used as a simple map key
+// Denomination is just the coin kinds with no extra data, which makes it easy to hash
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+enum Denomination {
+    Penny,
+    Nickel,
+    Dime,
+    Quarter,
+}
+
+// every denomination from largest to smallest, the order make_change needs
+const DENOMINATIONS: [Denomination; 4] = [
+    Denomination::Quarter,
+    Denomination::Dime,
+    Denomination::Nickel,
+    Denomination::Penny,
+];
+
+impl Denomination {
+    // match is exhaustive so adding a new denomination wont compile until it has a value
+    fn cents(&self) -> u32 {
+        match self {
+            Denomination::Penny => 1,
+            Denomination::Nickel => 5,
+            Denomination::Dime => 10,
+            Denomination::Quarter => 25,
+        }
+    }
+}
+
+// Greedily breaks an amount into coins, always taking as many of the biggest coin as fit
+// Every denomination is in the map, even if we need zero of it
+fn make_change(cents: u32) -> HashMap<Denomination, u32> {
+    let mut remaining = cents;
+    let mut change = HashMap::new();
+
+    for denomination in DENOMINATIONS {
+        let count = remaining / denomination.cents();
+        remaining -= count * denomination.cents();
+        change.insert(denomination, count);
+    }
+
+    change
+}
+
 #[derive(Debug)] // so we can inspect the state in a minute
 enum UsState {
     Alabama,
@@ -65,13 +137,217 @@ fn value_in_cents_two(coin: Coin) -> u8 {
 }
 
 // Function that uses matching on an optional value
-fn plus_one(x: Option<i32>) -> Option<i32> {
+// This is generic so it works on any number type we can add and build a 1 from
+// (T::from(1) works since i32, i64, u32, f64 etc all implement From<u8>)
+fn plus_one<T: Add<Output = T> + From<u8>>(x: Option<T>) -> Option<T> {
     match x {
         None => None,
-        Some(i) => Some(i + 1),
+        Some(i) => Some(i + T::from(1)),
+    }
+    // this match is exactly what Option::map does for us, so this would also work:
+    // x.map(|v| v + T::from(1))
+}
+
+// Arms are checked top to bottom and the first one that matches wins
+// a match guard (the if after a pattern) adds a condition the pattern alone cant say,
+// and @ binds the matched value to a name while still testing it against a range
+fn categorize(n: i32) -> &'static str {
+    match n {
+        x if x < 0 => "negative",
+        0 => "zero",
+        1..=9 => "single digit",
+        tens @ 10..=99 if tens % 10 == 0 => "round double digit",
+        10..=99 => "double digit",
+        _ => "large",
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+// match can pull a struct apart too, a literal in a field only matches that value
+// while a bare name binds whatever is there, the order of the arms matters
+// since the origin would also match the two axis arms
+fn describe_point(p: Point) -> String {
+    match p {
+        Point { x: 0, y: 0 } => String::from("at the origin"),
+        Point { x, y: 0 } => format!("on the x axis at {x}"),
+        Point { x: 0, y } => format!("on the y axis at {y}"),
+        Point { x, y } => format!("at ({x}, {y})"),
+    }
+}
+
+// A tiny math expression, an Expr can hold other Exprs so they have to be boxed
+// (just like the cons list, otherwise the enum would have an infinite size)
+#[derive(Debug)]
+enum Expr {
+    Num(f64),
+    Add(Box<Expr>, Box<Expr>),
+    Mul(Box<Expr>, Box<Expr>),
+    Neg(Box<Expr>),
+}
+
+// each arm pulls the boxed parts out of the variant and evaluates them first,
+// the recursion stops when it reaches a Num
+fn eval(e: &Expr) -> f64 {
+    match e {
+        Expr::Num(n) => *n,
+        Expr::Add(left, right) => eval(left) + eval(right),
+        Expr::Mul(left, right) => eval(left) * eval(right),
+        Expr::Neg(inner) => -eval(inner),
     }
 }
+
 fn add_fancy_hat() {}
 fn remove_fancy_hat() {}
 fn move_player(num_spaces: u8) {}
-fn reroll() {}
\ No newline at end of file
+fn reroll() {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn makes_change_for_99() {
+        let change = make_change(99);
+
+        assert_eq!(change[&Denomination::Quarter], 3);
+        assert_eq!(change[&Denomination::Dime], 2);
+        assert_eq!(change[&Denomination::Nickel], 0);
+        assert_eq!(change[&Denomination::Penny], 4);
+    }
+
+    #[test]
+    fn makes_change_using_every_coin() {
+        let change = make_change(41);
+
+        assert_eq!(change[&Denomination::Quarter], 1);
+        assert_eq!(change[&Denomination::Dime], 1);
+        assert_eq!(change[&Denomination::Nickel], 1);
+        assert_eq!(change[&Denomination::Penny], 1);
+    }
+
+    #[test]
+    fn makes_change_for_zero() {
+        let change = make_change(0);
+
+        assert_eq!(change.len(), DENOMINATIONS.len());
+        assert!(change.values().all(|&count| count == 0));
+    }
+
+    #[test]
+    fn change_adds_back_up() {
+        for cents in 0..200 {
+            let total: u32 = make_change(cents)
+                .iter()
+                .map(|(denomination, count)| denomination.cents() * count)
+                .sum();
+
+            assert_eq!(total, cents);
+        }
+    }
+
+    #[test]
+    fn plus_one_i32() {
+        assert_eq!(plus_one(Some(5)), Some(6));
+        assert_eq!(plus_one(Some(-1i32)), Some(0));
+    }
+
+    #[test]
+    fn plus_one_i64() {
+        assert_eq!(plus_one(Some(5_000_000_000i64)), Some(5_000_000_001));
+    }
+
+    #[test]
+    fn plus_one_f64() {
+        assert_eq!(plus_one(Some(1.5f64)), Some(2.5));
+    }
+
+    #[test]
+    fn plus_one_none() {
+        assert_eq!(plus_one::<i32>(None), None);
+        assert_eq!(plus_one::<i64>(None), None);
+    }
+
+    #[test]
+    fn categorize_negative() {
+        assert_eq!(categorize(-1), "negative");
+        assert_eq!(categorize(i32::MIN), "negative");
+    }
+
+    #[test]
+    fn categorize_single_digit() {
+        assert_eq!(categorize(0), "zero");
+        assert_eq!(categorize(1), "single digit");
+        assert_eq!(categorize(9), "single digit");
+    }
+
+    #[test]
+    fn categorize_double_digit_uses_the_bound_value() {
+        assert_eq!(categorize(10), "round double digit");
+        assert_eq!(categorize(90), "round double digit");
+        assert_eq!(categorize(42), "double digit");
+        assert_eq!(categorize(99), "double digit");
+    }
+
+    #[test]
+    fn categorize_large() {
+        assert_eq!(categorize(100), "large");
+        assert_eq!(categorize(i32::MAX), "large");
+    }
+
+    #[test]
+    fn describes_origin() {
+        assert_eq!(describe_point(Point { x: 0, y: 0 }), "at the origin");
+    }
+
+    #[test]
+    fn describes_points_on_an_axis() {
+        assert_eq!(describe_point(Point { x: 5, y: 0 }), "on the x axis at 5");
+        assert_eq!(describe_point(Point { x: 0, y: -3 }), "on the y axis at -3");
+    }
+
+    #[test]
+    fn describes_points_off_the_axes() {
+        assert_eq!(describe_point(Point { x: 2, y: 4 }), "at (2, 4)");
+        assert_eq!(describe_point(Point { x: -1, y: -1 }), "at (-1, -1)");
+    }
+
+    #[test]
+    fn eval_single_number() {
+        assert_eq!(eval(&Expr::Num(1.5)), 1.5);
+    }
+
+    #[test]
+    fn eval_nested_add_and_mul() {
+        // 2 + 3 * 4
+        let expr = Expr::Add(
+            Box::new(Expr::Num(2.0)),
+            Box::new(Expr::Mul(
+                Box::new(Expr::Num(3.0)),
+                Box::new(Expr::Num(4.0)),
+            )),
+        );
+
+        assert_eq!(eval(&expr), 14.0);
+    }
+
+    #[test]
+    fn eval_negation() {
+        // -(2 + 3) * 2
+        let expr = Expr::Mul(
+            Box::new(Expr::Neg(Box::new(Expr::Add(
+                Box::new(Expr::Num(2.0)),
+                Box::new(Expr::Num(3.0)),
+            )))),
+            Box::new(Expr::Num(2.0)),
+        );
+
+        assert_eq!(eval(&expr), -10.0);
+        let double_negative = Expr::Neg(Box::new(Expr::Neg(Box::new(Expr::Num(7.0)))));
+        assert_eq!(eval(&double_negative), 7.0);
+    }
+}
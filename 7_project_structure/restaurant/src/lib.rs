@@ -1,6 +1,67 @@
 mod front_of_house {
     pub mod hosting {
-        pub fn add_to_waitlist() {}
+        use std::fs;
+        use std::io;
+
+        // the parties waiting for a table, first in line first
+        #[derive(Default)]
+        pub struct Waitlist {
+            parties: Vec<String>,
+        }
+
+        impl Waitlist {
+            pub fn new() -> Waitlist {
+                Waitlist {
+                    parties: Vec::new(),
+                }
+            }
+
+            pub fn add(&mut self, party: &str) {
+                self.parties.push(String::from(party));
+            }
+
+            pub fn parties(&self) -> &[String] {
+                &self.parties
+            }
+
+            pub fn len(&self) -> usize {
+                self.parties.len()
+            }
+
+            pub fn is_empty(&self) -> bool {
+                self.parties.is_empty()
+            }
+
+            // one party name per line (so a name with a newline in it would come back as two parties)
+            pub fn save_to_file(&self, path: &str) -> io::Result<()> {
+                let mut contents = String::new();
+
+                for party in &self.parties {
+                    contents.push_str(party);
+                    contents.push('\n');
+                }
+
+                fs::write(path, contents)
+            }
+
+            // blank lines are skipped, so an empty file or extra trailing newlines
+            // dont turn into parties with no name
+            pub fn load_from_file(path: &str) -> io::Result<Waitlist> {
+                let contents = fs::read_to_string(path)?;
+
+                let parties = contents
+                    .lines()
+                    .filter(|line| !line.trim().is_empty())
+                    .map(String::from)
+                    .collect();
+
+                Ok(Waitlist { parties })
+            }
+        }
+
+        pub fn add_to_waitlist(waitlist: &mut Waitlist, party: &str) {
+            waitlist.add(party);
+        }
 
         fn seat_at_table() {}
     }
@@ -22,6 +83,8 @@ use crate::back_of_house::Appetizer as AppetizerFromBack;
 
 // we can also 're-export' imported modules to make them available for others to import
 // pub use crate::customer::eat_at_restaurant;
+// Waitlist lives in a private module but users of the crate can still get it as restaurant::Waitlist
+pub use crate::front_of_house::hosting::Waitlist;
 
 // we can also use public packages
 use rand::Rng;
@@ -47,18 +110,23 @@ fn main() {
 }
 
 mod customer {
-    pub fn eat_at_restaurant() {
-        // inside we cannot use the 'use' because we are now outside its scope
-        //hosting::add_to_waitlist();
+    // the 'use' at the top of the file doesnt reach in here since a use only applies to the module it is in
+    // so customer needs its own, super is the crate root where front_of_house lives
+    use super::front_of_house::hosting;
+
+    pub fn eat_at_restaurant(waitlist: &mut hosting::Waitlist, party: &str) {
+        hosting::add_to_waitlist(waitlist, party);
     }
 }
 
 pub fn eat_at_restaurant() {
+    let mut waitlist = Waitlist::new();
+
     // absolute path
-    crate::front_of_house::hosting::add_to_waitlist();
+    crate::front_of_house::hosting::add_to_waitlist(&mut waitlist, "Smith");
 
     // relative path
-    front_of_house::hosting::add_to_waitlist();
+    front_of_house::hosting::add_to_waitlist(&mut waitlist, "Garcia");
 
     // lets order
     let mut meal = back_of_house::Breakfast::summer("Rye Bread");
@@ -73,9 +141,22 @@ pub fn eat_at_restaurant() {
     let order1 = back_of_house::Appetizer::Soup;
     let order2 = back_of_house::Appetizer::Salad;
 
+    // a table of three gets a main each, then we work out the bill
+    use back_of_house::{MainCourse, MenuItem};
+    let bill = back_of_house::total(&[
+        MenuItem::Appetizer(order1),
+        MenuItem::Appetizer(order2),
+        MenuItem::Main(MainCourse::Burger),
+        MenuItem::Main(MainCourse::Pasta),
+        MenuItem::Main(MainCourse::Steak),
+    ]);
+    println!("That will be ${}.{:02}", bill / 100, bill % 100);
+
 
     // we can call the 'use' item like this
-    hosting::add_to_waitlist();
+    hosting::add_to_waitlist(&mut waitlist, "Lee");
+
+    println!("{} parties waiting", waitlist.len());
 }
 
 fn deliver_order() {}
@@ -107,4 +188,164 @@ mod back_of_house {
         Soup,
         Salad,
     }
-}
\ No newline at end of file
+
+    // prices are in cents so we never have to deal with float rounding on a bill
+    impl Appetizer {
+        pub fn price(&self) -> u32 {
+            match self {
+                Appetizer::Soup => 550,
+                Appetizer::Salad => 650,
+            }
+        }
+    }
+
+    pub enum MainCourse {
+        Burger,
+        Pasta,
+        Steak,
+    }
+
+    impl MainCourse {
+        pub fn price(&self) -> u32 {
+            match self {
+                MainCourse::Burger => 1299,
+                MainCourse::Pasta => 1450,
+                MainCourse::Steak => 2499,
+            }
+        }
+    }
+
+    // an order can have both kinds in it, so wrap each one in a variant
+    pub enum MenuItem {
+        Appetizer(Appetizer),
+        Main(MainCourse),
+    }
+
+    impl MenuItem {
+        pub fn price(&self) -> u32 {
+            match self {
+                MenuItem::Appetizer(appetizer) => appetizer.price(),
+                MenuItem::Main(main) => main.price(),
+            }
+        }
+    }
+
+    // adds up the bill for a whole order, in cents
+    pub fn total(order: &[MenuItem]) -> u32 {
+        order.iter().map(|item| item.price()).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{env, fs, process};
+
+    // a path in the temp dir that wont clash with other tests or other runs
+    fn temp_path(name: &str) -> String {
+        env::temp_dir()
+            .join(format!("restaurant_{name}_{}.txt", process::id()))
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    #[test]
+    fn waitlist_round_trips_through_a_file() {
+        let path = temp_path("round_trip");
+        let mut waitlist = Waitlist::new();
+        waitlist.add("Smith party of 4");
+        waitlist.add("Garcia");
+        waitlist.add("Lee, 2 people");
+
+        waitlist.save_to_file(&path).unwrap();
+        let loaded = Waitlist::load_from_file(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.parties(), waitlist.parties());
+    }
+
+    #[test]
+    fn empty_waitlist_round_trips() {
+        let path = temp_path("empty_round_trip");
+
+        Waitlist::new().save_to_file(&path).unwrap();
+        let loaded = Waitlist::load_from_file(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert!(loaded.is_empty());
+    }
+
+    #[test]
+    fn load_skips_blank_and_trailing_lines() {
+        let path = temp_path("blank_lines");
+        fs::write(&path, "Smith\n\nGarcia\n\n\n").unwrap();
+
+        let loaded = Waitlist::load_from_file(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded.parties(), ["Smith", "Garcia"]);
+    }
+
+    #[test]
+    fn load_handles_windows_line_endings() {
+        let path = temp_path("crlf");
+        fs::write(&path, "Smith\r\nGarcia\r\n").unwrap();
+
+        let loaded = Waitlist::load_from_file(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.parties(), ["Smith", "Garcia"]);
+    }
+
+    #[test]
+    fn load_missing_file_is_an_error() {
+        let result = Waitlist::load_from_file(&temp_path("does_not_exist"));
+
+        assert_eq!(result.err().map(|e| e.kind()), Some(std::io::ErrorKind::NotFound));
+    }
+
+    #[test]
+    fn each_item_has_a_price() {
+        use back_of_house::{Appetizer, MainCourse, MenuItem};
+
+        assert_eq!(Appetizer::Soup.price(), 550);
+        assert_eq!(Appetizer::Salad.price(), 650);
+        assert_eq!(MainCourse::Burger.price(), 1299);
+        assert_eq!(MainCourse::Pasta.price(), 1450);
+        assert_eq!(MainCourse::Steak.price(), 2499);
+
+        // wrapping an item in MenuItem doesnt change its price
+        assert_eq!(MenuItem::Main(MainCourse::Steak).price(), 2499);
+    }
+
+    #[test]
+    fn total_sums_an_order() {
+        use back_of_house::{Appetizer, MainCourse, MenuItem};
+
+        let order = [
+            MenuItem::Appetizer(Appetizer::Soup),
+            MenuItem::Main(MainCourse::Burger),
+            MenuItem::Main(MainCourse::Burger),
+            MenuItem::Appetizer(Appetizer::Salad),
+        ];
+
+        assert_eq!(back_of_house::total(&order), 550 + 1299 + 1299 + 650);
+    }
+
+    #[test]
+    fn total_of_nothing_is_zero() {
+        assert_eq!(back_of_house::total(&[]), 0);
+    }
+
+    #[test]
+    fn customer_eat_at_restaurant_joins_the_waitlist() {
+        let mut waitlist = Waitlist::new();
+        waitlist.add("Smith");
+
+        customer::eat_at_restaurant(&mut waitlist, "Garcia");
+
+        assert_eq!(waitlist.len(), 2);
+        assert_eq!(waitlist.parties(), ["Smith", "Garcia"]);
+    }
+}
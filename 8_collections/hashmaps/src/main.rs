@@ -1,4 +1,138 @@
 use std::collections::HashMap;
+use std::hash::Hash;
+
+// counts how many times each word shows up in some text
+// words are lowercased and have any surrounding punctuation stripped so
+// "World," and "world" end up in the same bucket
+fn word_frequencies(text: &str) -> HashMap<String, usize> {
+    let mut map = HashMap::new();
+
+    for word in text.split_whitespace() {
+        let word = word
+            .trim_matches(|c: char| c.is_ascii_punctuation())
+            .to_lowercase();
+
+        // a word made entirely of punctuation (like "--") has nothing left to count
+        if word.is_empty() {
+            continue;
+        }
+
+        let count = map.entry(word).or_insert(0);
+        *count += 1;
+    }
+
+    map
+}
+
+// gets the n most common words, highest count first
+// words with the same count are ordered alphabetically so the result is stable
+fn top_n(freqs: &HashMap<String, usize>, n: usize) -> Vec<(String, usize)> {
+    let mut counts: Vec<(String, usize)> = freqs
+        .iter()
+        .map(|(word, count)| (word.clone(), *count))
+        .collect();
+
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    counts.truncate(n);
+
+    counts
+}
+
+// writes the frequencies out as word,count rows (no header), most common first
+// uses the same ordering as top_n so ties come out alphabetically
+fn to_csv(freqs: &HashMap<String, usize>) -> String {
+    let mut csv = String::new();
+
+    for (word, count) in top_n(freqs, freqs.len()) {
+        csv.push_str(&csv_field(&word));
+        csv.push(',');
+        csv.push_str(&count.to_string());
+        csv.push('\n');
+    }
+
+    csv
+}
+
+// RFC 4180 quoting, a field with a comma, quote or line break has to be wrapped in quotes
+// and any quotes inside it are doubled up, anything else is written as is
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+// the same counting pattern as word_frequencies but for anything hashable
+// takes anything that can be turned into an iterator so a Vec, a slice iter, s.chars() etc all work
+fn histogram<T: Eq + Hash, I: IntoIterator<Item = T>>(items: I) -> HashMap<T, usize> {
+    let mut counts = HashMap::new();
+
+    for item in items {
+        *counts.entry(item).or_insert(0) += 1;
+    }
+
+    counts
+}
+
+// counts values into buckets bucket_size wide, bucket n holds n * bucket_size up to (n + 1) * bucket_size
+// f64 cant be a HashMap key (it isnt Eq or Hash) so the bucket number is used as the key instead
+// floor keeps negatives right, -0.5 goes in bucket -1 not bucket 0
+// NaN and infinity dont belong in any bucket so they are skipped
+fn bucketize(values: &[f64], bucket_size: f64) -> HashMap<i64, usize> {
+    assert!(bucket_size > 0.0, "bucket_size must be positive");
+
+    histogram(
+        values
+            .iter()
+            .filter(|v| v.is_finite())
+            .map(|v| (v / bucket_size).floor() as i64),
+    )
+}
+
+// counts pairs grouped by the first key then the second, like how many times each word shows up in each document
+// the outer entry gives us the inner map for that group (making an empty one the first time)
+// and then the inner entry is the same counting as histogram
+// the keys are cloned since we only borrow the pairs
+fn group_count<K1, K2>(pairs: &[(K1, K2)]) -> HashMap<K1, HashMap<K2, usize>>
+where
+    K1: Eq + Hash + Clone,
+    K2: Eq + Hash + Clone,
+{
+    let mut groups: HashMap<K1, HashMap<K2, usize>> = HashMap::new();
+
+    for (outer, inner) in pairs {
+        *groups
+            .entry(outer.clone())
+            .or_default()
+            .entry(inner.clone())
+            .or_insert(0) += 1;
+    }
+
+    groups
+}
+
+// combines two sets of scores, adding them together when both have the same team
+// we take ownership of both maps so we can reuse a's storage and move b's keys in
+fn merge_sum(a: HashMap<String, i32>, b: HashMap<String, i32>) -> HashMap<String, i32> {
+    let mut merged = a;
+
+    for (key, value) in b {
+        *merged.entry(key).or_insert(0) += value;
+    }
+
+    merged
+}
+
+// swaps keys and values, V now needs Eq + Hash since it becomes the key
+// if two keys share a value only one of them survives (whichever is inserted last)
+fn invert<K, V>(map: HashMap<K, V>) -> HashMap<V, K>
+where
+    V: Eq + Hash,
+{
+    map.into_iter().map(|(key, value)| (value, key)).collect()
+}
+
 fn main() {
     // lets make a new hashmap and insert some values
     let mut scores = HashMap::new();
@@ -27,15 +161,297 @@ fn main() {
 
     println!("{scores:?}");
 
+    // we can merge another round of scores into the first
+    let mut round_two = HashMap::new();
+    round_two.insert(String::from("Blue"), 5);
+    round_two.insert(String::from("Red"), 30);
+
+    let totals = merge_sum(scores, round_two);
+    println!("{totals:?}");
+
+    // or flip it around to look up a team by its score
+    let by_score = invert(totals);
+    println!("{by_score:?}");
+
     // get and update old values
     let text = "hello world wonderful world";
 
-    let mut map = HashMap::new();
+    let map = word_frequencies(text);
 
-    for word in text.split_whitespace() {
-        let count = map.entry(word).or_insert(0);
-        *count += 1;
+    println!("{map:?}");
+
+    // and then find the most common ones
+    println!("{:?}", top_n(&map, 2));
+
+    // counting works on anything hashable, like the letters in a word
+    println!("{:?}", histogram("hello".chars()));
+    println!("{:?}", bucketize(&[0.5, 1.5, 1.9, 7.2], 1.0));
+
+    // or count within groups
+    let sales = [
+        ("fruit", "apple"),
+        ("fruit", "pear"),
+        ("fruit", "apple"),
+        ("veg", "leek"),
+    ];
+    println!("{:?}", group_count(&sales));
+
+    // or export the whole lot as csv
+    print!("{}", to_csv(&map));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PARAGRAPH: &str = "The cat sat on the mat. The dog sat too! \
+        A cat, a dog -- and the mat.";
+
+    #[test]
+    fn merge_sum_adds_overlapping_keys() {
+        let mut a = HashMap::new();
+        a.insert(String::from("Blue"), 10);
+        a.insert(String::from("Yellow"), 50);
+
+        let mut b = HashMap::new();
+        b.insert(String::from("Blue"), 15);
+        b.insert(String::from("Red"), -5);
+
+        let merged = merge_sum(a, b);
+
+        assert_eq!(merged.len(), 3);
+        assert_eq!(merged.get("Blue"), Some(&25));
+        assert_eq!(merged.get("Yellow"), Some(&50));
+        assert_eq!(merged.get("Red"), Some(&-5));
     }
 
-    println!("{map:?}");
+    #[test]
+    fn merge_sum_with_empty_is_unchanged() {
+        let mut a = HashMap::new();
+        a.insert(String::from("Blue"), 10);
+
+        let merged = merge_sum(a.clone(), HashMap::new());
+
+        assert_eq!(merged, a);
+    }
+
+    #[test]
+    fn invert_swaps_keys_and_values() {
+        let mut map = HashMap::new();
+        map.insert("one", 1);
+        map.insert("two", 2);
+
+        let inverted = invert(map);
+
+        assert_eq!(inverted.get(&1), Some(&"one"));
+        assert_eq!(inverted.get(&2), Some(&"two"));
+    }
+
+    #[test]
+    fn invert_round_trips_a_bijective_map() {
+        let mut map = HashMap::new();
+        map.insert(String::from("Blue"), 10);
+        map.insert(String::from("Yellow"), 50);
+        map.insert(String::from("Red"), 30);
+
+        let round_tripped = invert(invert(map.clone()));
+
+        assert_eq!(round_tripped, map);
+    }
+
+    #[test]
+    fn counts_words() {
+        let freqs = word_frequencies(PARAGRAPH);
+
+        assert_eq!(freqs.get("the"), Some(&4));
+        assert_eq!(freqs.get("cat"), Some(&2));
+        assert_eq!(freqs.get("sat"), Some(&2));
+        assert_eq!(freqs.get("too"), Some(&1));
+        assert_eq!(freqs.get("missing"), None);
+    }
+
+    #[test]
+    fn lowercases_and_strips_punctuation() {
+        let freqs = word_frequencies(PARAGRAPH);
+
+        // "mat." and "mat." both count as "mat"
+        assert_eq!(freqs.get("mat"), Some(&2));
+        // "The" and "the" are the same word
+        assert_eq!(freqs.get("The"), None);
+        // "--" is all punctuation so it is dropped entirely
+        assert_eq!(freqs.get("--"), None);
+        assert_eq!(freqs.get(""), None);
+    }
+
+    #[test]
+    fn top_n_orders_by_count_then_alphabetically() {
+        let freqs = word_frequencies(PARAGRAPH);
+
+        assert_eq!(
+            top_n(&freqs, 6),
+            vec![
+                (String::from("the"), 4),
+                (String::from("a"), 2),
+                (String::from("cat"), 2),
+                (String::from("dog"), 2),
+                (String::from("mat"), 2),
+                (String::from("sat"), 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn top_n_larger_than_map_returns_everything() {
+        let freqs = word_frequencies("one two two");
+
+        assert_eq!(
+            top_n(&freqs, 10),
+            vec![(String::from("two"), 2), (String::from("one"), 1)]
+        );
+    }
+
+    #[test]
+    fn top_n_of_zero_is_empty() {
+        let freqs = word_frequencies(PARAGRAPH);
+
+        assert!(top_n(&freqs, 0).is_empty());
+    }
+
+    #[test]
+    fn to_csv_orders_by_count() {
+        let freqs = word_frequencies("one two two three three three");
+
+        assert_eq!(to_csv(&freqs), "three,3\ntwo,2\none,1\n");
+    }
+
+    #[test]
+    fn to_csv_breaks_ties_alphabetically() {
+        let freqs = word_frequencies("b a c a");
+
+        assert_eq!(to_csv(&freqs), "a,2\nb,1\nc,1\n");
+    }
+
+    #[test]
+    fn to_csv_quotes_commas_and_quotes() {
+        let mut freqs = HashMap::new();
+        freqs.insert(String::from("salt,pepper"), 2);
+        freqs.insert(String::from("say \"hi\""), 1);
+
+        assert_eq!(to_csv(&freqs), "\"salt,pepper\",2\n\"say \"\"hi\"\"\",1\n");
+    }
+
+    #[test]
+    fn to_csv_keeps_inner_commas_from_word_frequencies() {
+        // only surrounding punctuation is stripped so the comma inside survives
+        let freqs = word_frequencies("1,000 1,000 dollars");
+
+        assert_eq!(to_csv(&freqs), "\"1,000\",2\ndollars,1\n");
+    }
+
+    #[test]
+    fn to_csv_of_empty_map_is_empty() {
+        assert_eq!(to_csv(&HashMap::new()), "");
+    }
+
+    #[test]
+    fn histogram_of_chars() {
+        let counts = histogram("mississippi".chars());
+
+        assert_eq!(counts.len(), 4);
+        assert_eq!(counts[&'m'], 1);
+        assert_eq!(counts[&'i'], 4);
+        assert_eq!(counts[&'s'], 4);
+        assert_eq!(counts[&'p'], 2);
+    }
+
+    #[test]
+    fn histogram_of_a_vec_and_of_nothing() {
+        let counts = histogram(vec![3, 1, 3, 3]);
+        assert_eq!(counts[&3], 3);
+        assert_eq!(counts[&1], 1);
+
+        assert!(histogram(Vec::<i32>::new()).is_empty());
+    }
+
+    #[test]
+    fn bucketize_floats() {
+        let buckets = bucketize(&[0.1, 0.9, 1.0, 2.5, 2.7, 9.99], 1.0);
+
+        assert_eq!(buckets.len(), 4);
+        assert_eq!(buckets[&0], 2);
+        // 1.0 is the start of bucket 1 not the end of bucket 0
+        assert_eq!(buckets[&1], 1);
+        assert_eq!(buckets[&2], 2);
+        assert_eq!(buckets[&9], 1);
+    }
+
+    #[test]
+    fn bucketize_wider_buckets_and_negatives() {
+        let buckets = bucketize(&[-12.0, -0.5, 3.0, 24.9, 25.0], 25.0);
+
+        assert_eq!(buckets[&-1], 2);
+        assert_eq!(buckets[&0], 2);
+        assert_eq!(buckets[&1], 1);
+    }
+
+    #[test]
+    fn bucketize_skips_nan_and_infinity() {
+        let buckets = bucketize(&[f64::NAN, f64::INFINITY, 0.5], 1.0);
+
+        assert_eq!(buckets.len(), 1);
+        assert_eq!(buckets[&0], 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn bucketize_zero_width_panics() {
+        bucketize(&[1.0], 0.0);
+    }
+
+    #[test]
+    fn group_count_counts_within_each_group() {
+        let pairs = [
+            ("fruit", "apple"),
+            ("veg", "carrot"),
+            ("fruit", "pear"),
+            ("fruit", "apple"),
+            ("veg", "carrot"),
+            ("dairy", "milk"),
+        ];
+
+        let groups = group_count(&pairs);
+
+        assert_eq!(groups.len(), 3);
+        assert_eq!(groups["fruit"].len(), 2);
+        assert_eq!(groups["fruit"]["apple"], 2);
+        assert_eq!(groups["fruit"]["pear"], 1);
+        assert_eq!(groups["veg"]["carrot"], 2);
+        assert_eq!(groups["dairy"]["milk"], 1);
+        // an item only counts in the group it showed up in
+        assert_eq!(groups["veg"].get("apple"), None);
+    }
+
+    #[test]
+    fn group_count_words_per_document() {
+        let mut pairs = vec![];
+        for (doc, text) in [(1, "the cat the hat"), (2, "a cat")] {
+            for word in text.split_whitespace() {
+                pairs.push((doc, String::from(word)));
+            }
+        }
+
+        let groups = group_count(&pairs);
+
+        assert_eq!(groups[&1]["the"], 2);
+        assert_eq!(groups[&1]["cat"], 1);
+        assert_eq!(groups[&2]["cat"], 1);
+        assert_eq!(groups[&2].get("the"), None);
+    }
+
+    #[test]
+    fn group_count_of_nothing() {
+        let pairs: [(i32, i32); 0] = [];
+
+        assert!(group_count(&pairs).is_empty());
+    }
 }
@@ -1,3 +1,62 @@
+use unicode_segmentation::UnicodeSegmentation;
+
+// counts what a person would actually call a "character" (a grapheme cluster)
+// .len() counts bytes and .chars() counts unicode scalar values, neither of which
+// line up with what you see for things like "नमस्ते" or a flag emoji
+fn grapheme_count(s: &str) -> usize {
+    // the true flag means we want extended grapheme clusters (the modern definition)
+    s.graphemes(true).count()
+}
+
+// slices by char position instead of byte position
+// &s[0..4] works on bytes and will panic if it lands in the middle of a char,
+// this walks the chars instead so it can never split one
+fn char_slice(s: &str, start: usize, end: usize) -> Option<String> {
+    if start > end || end > s.chars().count() {
+        return None;
+    }
+
+    Some(s.chars().skip(start).take(end - start).collect())
+}
+
+// reverses what you see on screen, grapheme by grapheme
+// reversing the bytes would scramble every multibyte char into invalid UTF-8, and even
+// reversing chars moves a combining accent onto the wrong letter ("cafe\u{301}" -> "\u{301}efac")
+// keeping each grapheme cluster together as one unit avoids both
+fn reverse_graphemes(s: &str) -> String {
+    s.graphemes(true).rev().collect()
+}
+
+// case insensitive contains, like minigrep's to_lowercase search but folding one char at a time
+// str::to_lowercase looks at context, a capital Σ at the end of a word becomes the final form ς
+// and anywhere else becomes σ, so the same letter can lowercase two ways depending on where you cut
+// the string ("ΑΣ" -> "ας" but "ΑΣΑ" -> "ασα", so naively "ΑΣΑ" doesnt contain "ΑΣ")
+// folding each char on its own and treating ς as σ gives the same answer wherever it appears
+// note: this is not locale aware, Turkish dotted İ folds to i plus a combining dot (so it wont match
+// a plain "i") and dotless ı stays ı, and expansions like ß -> ss are left out entirely
+fn contains_fold(haystack: &str, needle: &str) -> bool {
+    fold_case(haystack).contains(&fold_case(needle))
+}
+
+fn fold_case(s: &str) -> String {
+    s.chars()
+        .flat_map(char::to_lowercase)
+        .map(|c| if c == 'ς' { 'σ' } else { c })
+        .collect()
+}
+
+// checks if the letters and numbers read the same both ways, ignoring case, spaces and punctuation
+// goes char by char so it works on any UTF-8 text, comparing bytes would reverse the bytes inside each char
+fn is_palindrome(s: &str) -> bool {
+    let letters: Vec<char> = s
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect();
+
+    letters.iter().eq(letters.iter().rev())
+}
+
 fn main() {
     // lets make a new empty string
     let mut s = String::new();
@@ -71,4 +130,229 @@ fn main() {
     for b in "Зд".bytes() {
         println!("{b}");
     }
+
+    // if we want to slice by chars rather than bytes we can use our helper
+    // this gets the same 'Зд' but without needing to know each char is 2 bytes
+    if let Some(s) = char_slice(hello, 0, 2) {
+        println!("{s}");
+    }
+
+    // if we want what a human would call characters we need grapheme clusters
+    // "नमस्ते" is 18 bytes, 6 chars but only 3 graphemes (न, म, स्ते)
+    let hindi = "नमस्ते";
+    println!(
+        "{hindi}: {} bytes, {} chars, {} graphemes",
+        hindi.len(),
+        hindi.chars().count(),
+        grapheme_count(hindi)
+    );
+
+    // reversing has the same problem, chars split the accent off its letter but graphemes dont
+    let cafe = "cafe\u{301}";
+    println!(
+        "{cafe} reversed by chars is {} but by graphemes is {}",
+        cafe.chars().rev().collect::<String>(),
+        reverse_graphemes(cafe)
+    );
+
+    // case insensitive search that gets the Greek sigma right
+    println!("ΑΣΑ contains ΑΣ: {}", contains_fold("ΑΣΑ", "ΑΣ"));
+
+    let phrase = "A man, a plan, a canal: Panama";
+    println!("{phrase:?} is a palindrome: {}", is_palindrome(phrase));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slices_multibyte_by_char() {
+        let s = "Здравствуйте";
+
+        // the same as &s[0..4] but using char positions
+        assert_eq!(char_slice(s, 0, 2), Some(String::from("Зд")));
+        assert_eq!(char_slice(s, 2, 6), Some(String::from("равс")));
+        assert_eq!(char_slice(s, 0, 12), Some(String::from(s)));
+    }
+
+    #[test]
+    fn slices_mixed_width_chars() {
+        // 1, 2, 3 and 4 byte chars all in one string
+        let s = "aé中😀";
+
+        assert_eq!(char_slice(s, 1, 3), Some(String::from("é中")));
+        assert_eq!(char_slice(s, 3, 4), Some(String::from("😀")));
+    }
+
+    #[test]
+    fn empty_range_gives_empty_string() {
+        assert_eq!(char_slice("Здравствуйте", 3, 3), Some(String::new()));
+        assert_eq!(char_slice("", 0, 0), Some(String::new()));
+    }
+
+    #[test]
+    fn out_of_bounds_is_none() {
+        let s = "Здравствуйте";
+
+        // &s[0..13] would panic here, we get None instead
+        assert_eq!(char_slice(s, 0, 13), None);
+        assert_eq!(char_slice(s, 20, 25), None);
+    }
+
+    #[test]
+    fn backwards_range_is_none() {
+        assert_eq!(char_slice("Здравствуйте", 4, 2), None);
+    }
+
+    #[test]
+    fn counts_ascii_the_same_every_way() {
+        let s = "Hola";
+
+        assert_eq!(s.len(), 4);
+        assert_eq!(s.chars().count(), 4);
+        assert_eq!(grapheme_count(s), 4);
+    }
+
+    #[test]
+    fn counts_hindi_graphemes() {
+        let s = "नमस्ते";
+
+        // each devanagari char is 3 bytes
+        assert_eq!(s.len(), 18);
+        // the diacritics are their own chars
+        assert_eq!(s.chars().count(), 6);
+        // but they combine into 3 user perceived characters: न, म and the conjunct स्ते
+        assert_eq!(grapheme_count(s), 3);
+    }
+
+    #[test]
+    fn counts_flag_emoji_as_one() {
+        // a flag is two regional indicator symbols next to each other
+        let s = "🇺🇸";
+
+        assert_eq!(s.len(), 8);
+        assert_eq!(s.chars().count(), 2);
+        assert_eq!(grapheme_count(s), 1);
+    }
+
+    #[test]
+    fn counts_emoji_with_modifier_as_one() {
+        // waving hand followed by a skin tone modifier
+        let s = "👋🏽";
+
+        assert_eq!(s.chars().count(), 2);
+        assert_eq!(grapheme_count(s), 1);
+    }
+
+    #[test]
+    fn counts_combining_accent_as_one() {
+        // 'e' followed by a combining acute accent
+        let s = "e\u{301}";
+
+        assert_eq!(s.chars().count(), 2);
+        assert_eq!(grapheme_count(s), 1);
+    }
+
+    #[test]
+    fn reverses_ascii() {
+        assert_eq!(reverse_graphemes("hello"), "olleh");
+        assert_eq!(reverse_graphemes(""), "");
+    }
+
+    #[test]
+    fn reverses_cyrillic() {
+        assert_eq!(reverse_graphemes("Привет"), "тевирП");
+    }
+
+    #[test]
+    fn naive_byte_reversal_breaks_cyrillic() {
+        // each of these chars is 2 bytes, flipping the bytes leaves them in the wrong order
+        let bytes: Vec<u8> = "Привет".bytes().rev().collect();
+
+        assert!(String::from_utf8(bytes).is_err());
+    }
+
+    #[test]
+    fn keeps_combining_accent_on_its_letter() {
+        let s = "cafe\u{301}";
+
+        assert_eq!(reverse_graphemes(s), "e\u{301}fac");
+
+        // reversing chars puts the accent first, where it has no letter to sit on
+        let by_chars: String = s.chars().rev().collect();
+        assert_eq!(by_chars, "\u{301}efac");
+    }
+
+    #[test]
+    fn reverses_emoji_and_hindi_as_whole_units() {
+        assert_eq!(reverse_graphemes("a🇺🇸b"), "b🇺🇸a");
+        assert_eq!(reverse_graphemes("नमस्ते"), "स्तेमन");
+    }
+
+    #[test]
+    fn contains_fold_ignores_case_on_accents() {
+        assert!(contains_fold("Un CAFÉ noir", "café"));
+        assert!(contains_fold("crème brûlée", "BRÛLÉE"));
+        assert!(!contains_fold("cafe", "café"));
+    }
+
+    #[test]
+    fn contains_fold_mixed_case_cyrillic() {
+        assert!(contains_fold("ПрИвЕт МиР", "привет мир"));
+        assert!(contains_fold("здравствуйте", "СТВУЙ"));
+        assert!(!contains_fold("Привет", "пока"));
+    }
+
+    #[test]
+    fn contains_fold_handles_final_sigma() {
+        // naive lowercasing turns the needle's last Σ into ς but the haystack's into σ
+        assert!(!"ΑΣΑ".to_lowercase().contains(&"ΑΣ".to_lowercase()));
+        assert!(contains_fold("ΑΣΑ", "ΑΣ"));
+        assert!(contains_fold("ΟΔΥΣΣΕΥΣ", "οδυσσευσ"));
+    }
+
+    #[test]
+    fn contains_fold_empty_needle() {
+        assert!(contains_fold("anything", ""));
+        assert!(contains_fold("", ""));
+    }
+
+    #[test]
+    fn contains_fold_turkish_i_is_not_special_cased() {
+        // İ folds to "i\u{307}" so a plain i right before the s doesnt line up
+        assert!(!contains_fold("İstanbul", "istanbul"));
+        assert!(contains_fold("İstanbul", "stanbul"));
+        // dotless ı isnt the lowercase of I here either
+        assert!(!contains_fold("ıI", "ii"));
+    }
+
+    #[test]
+    fn canonical_palindrome() {
+        assert!(is_palindrome("A man, a plan, a canal: Panama"));
+        assert!(is_palindrome("racecar"));
+    }
+
+    #[test]
+    fn not_a_palindrome() {
+        assert!(!is_palindrome("hello"));
+        assert!(!is_palindrome("A man, a plan, a canal: Suez"));
+    }
+
+    #[test]
+    fn multibyte_palindrome() {
+        // "the rose fell on Azor's paw" in Russian
+        assert!(is_palindrome("А роза упала на лапу Азора"));
+        assert!(is_palindrome("été"));
+        assert!(!is_palindrome("Привет"));
+    }
+
+    #[test]
+    fn numbers_count_and_empty_is_a_palindrome() {
+        assert!(is_palindrome("12 - 21"));
+        assert!(!is_palindrome("123"));
+        // nothing left after filtering reads the same both ways
+        assert!(is_palindrome(""));
+        assert!(is_palindrome("?!"));
+    }
 }
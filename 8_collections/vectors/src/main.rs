@@ -1,3 +1,34 @@
+use std::collections::HashSet;
+use std::hash::Hash;
+
+mod matrix;
+mod min_heap;
+
+use matrix::Matrix;
+use min_heap::MinHeap;
+
+// removes every repeat, not just ones next to each other like Vec::dedup does
+// the HashSet remembers what we have already kept so the first time we see something wins
+fn dedup_preserve_order<T: Eq + Hash + Clone>(v: &[T]) -> Vec<T> {
+    let mut seen = HashSet::new();
+
+    v.iter()
+        .filter(|item| seen.insert(*item))
+        .cloned()
+        .collect()
+}
+
+// like slice.chunks but each chunk is its own Vec, so the result doesnt borrow from v
+// the last chunk is shorter when v doesnt divide evenly
+// chunks(0) would panic so a size of 0 just gives back no chunks
+fn chunks_owned<T: Clone>(v: &[T], size: usize) -> Vec<Vec<T>> {
+    if size == 0 {
+        return Vec::new();
+    }
+
+    v.chunks(size).map(|chunk| chunk.to_vec()).collect()
+}
+
 fn main() {
     // creating a new vector
     // Vect<T> will hold i32 type
@@ -57,6 +88,35 @@ fn main() {
         // do stuff with v
     } // <- v goes out of scope and is freed here
 
+    // a vector can also be used as a tree, MinHeap always pops the smallest value first
+    let mut heap = MinHeap::new();
+    for n in [5, 1, 4, 2, 3] {
+        heap.push(n);
+    }
+    println!("{} values, smallest is {:?}", heap.len(), heap.peek());
+    while let Some(n) = heap.pop() {
+        println!("popped {n}");
+    }
+    println!("heap empty: {}", heap.is_empty());
+
+    // dedup only catches repeats that are next to each other
+    let mut repeats = vec![1, 2, 1, 1, 3, 2];
+    println!("{:?}", dedup_preserve_order(&repeats));
+    repeats.dedup();
+    println!("{repeats:?}");
+
+    // split into owned pieces
+    println!("{:?}", chunks_owned(&[1, 2, 3, 4, 5], 2));
+
+    // and a vector of vectors makes a grid
+    if let Ok(mut grid) = Matrix::new(2, 3) {
+        grid.set(0, 2, 1.5).unwrap();
+        let flipped = grid.transpose();
+        println!("{grid:?} transposed is {flipped:?}");
+        println!("(2, 0) of the transpose is {:?}", flipped.get(2, 0));
+        println!("{:?}", Matrix::from_rows(vec![vec![1.0], vec![2.0, 3.0]]));
+    }
+
 }   
 
 enum SpreadSheetCell {
@@ -64,3 +124,74 @@ enum SpreadSheetCell {
     Float(f64),
     Text(String),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dedup_keeps_first_occurrence() {
+        let v = vec![3, 1, 3, 2, 1, 4, 2];
+
+        assert_eq!(dedup_preserve_order(&v), vec![3, 1, 2, 4]);
+    }
+
+    #[test]
+    fn dedup_differs_from_vec_dedup() {
+        let v = vec!["a", "b", "a", "a"];
+
+        let mut consecutive = v.clone();
+        consecutive.dedup();
+
+        assert_eq!(consecutive, vec!["a", "b", "a"]);
+        assert_eq!(dedup_preserve_order(&v), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn dedup_of_unique_vec_is_unchanged() {
+        let v = vec![String::from("x"), String::from("y"), String::from("z")];
+
+        assert_eq!(dedup_preserve_order(&v), v);
+    }
+
+    #[test]
+    fn dedup_of_empty() {
+        let v: Vec<i32> = vec![];
+
+        assert!(dedup_preserve_order(&v).is_empty());
+    }
+
+    #[test]
+    fn chunks_owned_even_split() {
+        assert_eq!(
+            chunks_owned(&[1, 2, 3, 4, 5, 6], 3),
+            vec![vec![1, 2, 3], vec![4, 5, 6]]
+        );
+    }
+
+    #[test]
+    fn chunks_owned_uneven_split() {
+        assert_eq!(
+            chunks_owned(&[1, 2, 3, 4, 5], 2),
+            vec![vec![1, 2], vec![3, 4], vec![5]]
+        );
+        // a size bigger than the slice is one chunk of everything
+        assert_eq!(chunks_owned(&[1, 2], 10), vec![vec![1, 2]]);
+    }
+
+    #[test]
+    fn chunks_owned_zero_size_is_empty() {
+        assert!(chunks_owned(&[1, 2, 3], 0).is_empty());
+    }
+
+    #[test]
+    fn chunks_owned_outlive_the_source() {
+        let chunks = {
+            let words = vec![String::from("a"), String::from("b"), String::from("c")];
+            chunks_owned(&words, 2)
+        };
+
+        // words is gone but the chunks own their own copies
+        assert_eq!(chunks, vec![vec!["a", "b"], vec!["c"]]);
+    }
+}
@@ -0,0 +1,165 @@
+// A grid of numbers stored as a vector of rows, each row is its own Vec<f64>
+// every row is kept the same length so data[r][c] is always the value at row r, column c
+#[derive(Debug, PartialEq)]
+pub struct Matrix {
+    data: Vec<Vec<f64>>,
+}
+
+impl Matrix {
+    // a rows x cols matrix of zeros
+    // an empty matrix is rejected since with no rows there would be nowhere to remember cols
+    pub fn new(rows: usize, cols: usize) -> Result<Matrix, String> {
+        if rows == 0 || cols == 0 {
+            return Err(format!("matrix must be at least 1x1, got {rows}x{cols}"));
+        }
+
+        Ok(Matrix {
+            data: vec![vec![0.0; cols]; rows],
+        })
+    }
+
+    // builds a matrix from rows we already have, they all have to be the same length
+    pub fn from_rows(data: Vec<Vec<f64>>) -> Result<Matrix, String> {
+        let cols = data.first().map_or(0, |row| row.len());
+
+        if cols == 0 {
+            return Err(String::from("matrix must have at least one row and column"));
+        }
+
+        if let Some(r) = data.iter().position(|row| row.len() != cols) {
+            return Err(format!(
+                "row {r} has {} columns but row 0 has {cols}",
+                data[r].len()
+            ));
+        }
+
+        Ok(Matrix { data })
+    }
+
+    pub fn rows(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn cols(&self) -> usize {
+        self.data[0].len()
+    }
+
+    // get on a Vec gives None instead of panicking, chaining two of them checks both indexes
+    pub fn get(&self, r: usize, c: usize) -> Result<f64, String> {
+        self.data
+            .get(r)
+            .and_then(|row| row.get(c))
+            .copied()
+            .ok_or_else(|| self.out_of_bounds(r, c))
+    }
+
+    pub fn set(&mut self, r: usize, c: usize, value: f64) -> Result<(), String> {
+        let message = self.out_of_bounds(r, c);
+
+        let cell = self
+            .data
+            .get_mut(r)
+            .and_then(|row| row.get_mut(c))
+            .ok_or(message)?;
+        *cell = value;
+
+        Ok(())
+    }
+
+    // flips rows and columns, row r of the result is column r of self
+    pub fn transpose(&self) -> Matrix {
+        let data = (0..self.cols())
+            .map(|c| self.data.iter().map(|row| row[c]).collect())
+            .collect();
+
+        Matrix { data }
+    }
+
+    fn out_of_bounds(&self, r: usize, c: usize) -> String {
+        format!(
+            "({r}, {c}) is outside a {}x{} matrix",
+            self.rows(),
+            self.cols()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_is_all_zeros() {
+        let m = Matrix::new(2, 3).unwrap();
+
+        assert_eq!((m.rows(), m.cols()), (2, 3));
+        assert_eq!(m.get(1, 2), Ok(0.0));
+    }
+
+    #[test]
+    fn new_rejects_empty_dimensions() {
+        assert!(Matrix::new(0, 3).is_err());
+        assert!(Matrix::new(3, 0).is_err());
+    }
+
+    #[test]
+    fn from_rows_rejects_ragged_rows() {
+        let result = Matrix::from_rows(vec![vec![1.0, 2.0], vec![3.0]]);
+
+        assert_eq!(
+            result,
+            Err(String::from("row 1 has 1 columns but row 0 has 2"))
+        );
+        assert!(Matrix::from_rows(vec![]).is_err());
+        assert!(Matrix::from_rows(vec![vec![]]).is_err());
+    }
+
+    #[test]
+    fn set_then_get() {
+        let mut m = Matrix::new(2, 2).unwrap();
+
+        m.set(0, 1, 4.5).unwrap();
+
+        assert_eq!(m.get(0, 1), Ok(4.5));
+        assert_eq!(m.get(1, 0), Ok(0.0));
+    }
+
+    #[test]
+    fn out_of_bounds_is_an_error() {
+        let mut m = Matrix::new(2, 3).unwrap();
+
+        assert_eq!(
+            m.get(2, 0),
+            Err(String::from("(2, 0) is outside a 2x3 matrix"))
+        );
+        assert!(m.get(0, 3).is_err());
+        assert!(m.set(5, 5, 1.0).is_err());
+        // nothing was changed by the failed set
+        assert_eq!(m, Matrix::new(2, 3).unwrap());
+    }
+
+    #[test]
+    fn transpose_non_square() {
+        let m = Matrix::from_rows(vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]]).unwrap();
+
+        let t = m.transpose();
+
+        assert_eq!((t.rows(), t.cols()), (3, 2));
+        assert_eq!(
+            t,
+            Matrix::from_rows(vec![vec![1.0, 4.0], vec![2.0, 5.0], vec![3.0, 6.0]]).unwrap()
+        );
+        for r in 0..m.rows() {
+            for c in 0..m.cols() {
+                assert_eq!(m.get(r, c), t.get(c, r));
+            }
+        }
+    }
+
+    #[test]
+    fn transpose_twice_is_the_original() {
+        let m = Matrix::from_rows(vec![vec![1.0], vec![2.0], vec![3.0]]).unwrap();
+
+        assert_eq!(m.transpose().transpose(), m);
+    }
+}
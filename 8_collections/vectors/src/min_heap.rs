@@ -0,0 +1,159 @@
+// A min heap stored in a plain Vec
+// The Vec is a complete binary tree laid out level by level, so for the item at index i
+// its children are at 2i + 1 and 2i + 2 and its parent is at (i - 1) / 2
+// The one rule is every parent is <= its children, which puts the smallest item at index 0
+pub struct MinHeap<T: Ord> {
+    items: Vec<T>,
+}
+
+impl<T: Ord> MinHeap<T> {
+    pub fn new() -> MinHeap<T> {
+        MinHeap { items: Vec::new() }
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    // the smallest item is always at the root
+    pub fn peek(&self) -> Option<&T> {
+        self.items.first()
+    }
+
+    // add to the end (the next free spot in the tree) then move it up until its parent is smaller
+    pub fn push(&mut self, item: T) {
+        self.items.push(item);
+        self.sift_up(self.items.len() - 1);
+    }
+
+    // swap the root with the last item so it can be popped off the Vec cheaply,
+    // then move the new root down until both children are bigger
+    pub fn pop(&mut self) -> Option<T> {
+        if self.items.is_empty() {
+            return None;
+        }
+
+        let last = self.items.len() - 1;
+        self.items.swap(0, last);
+        let min = self.items.pop();
+
+        if !self.items.is_empty() {
+            self.sift_down(0);
+        }
+
+        min
+    }
+
+    fn sift_up(&mut self, mut index: usize) {
+        while index > 0 {
+            let parent = (index - 1) / 2;
+
+            if self.items[index] >= self.items[parent] {
+                break;
+            }
+
+            self.items.swap(index, parent);
+            index = parent;
+        }
+    }
+
+    fn sift_down(&mut self, mut index: usize) {
+        let len = self.items.len();
+
+        loop {
+            let left = 2 * index + 1;
+            let right = left + 1;
+
+            // find whichever of index and its children is the smallest
+            let mut smallest = index;
+            if left < len && self.items[left] < self.items[smallest] {
+                smallest = left;
+            }
+            if right < len && self.items[right] < self.items[smallest] {
+                smallest = right;
+            }
+
+            // already smaller than both children (or has none) so it is in the right place
+            if smallest == index {
+                break;
+            }
+
+            self.items.swap(index, smallest);
+            index = smallest;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn drain<T: Ord>(heap: &mut MinHeap<T>) -> Vec<T> {
+        let mut out = vec![];
+        while let Some(item) = heap.pop() {
+            out.push(item);
+        }
+        out
+    }
+
+    #[test]
+    fn pops_shuffled_input_in_ascending_order() {
+        let mut heap = MinHeap::new();
+
+        for n in [7, 3, 9, 1, 8, 2, 6, 4, 10, 5] {
+            heap.push(n);
+        }
+
+        assert_eq!(heap.len(), 10);
+        assert_eq!(drain(&mut heap), (1..=10).collect::<Vec<i32>>());
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    fn keeps_duplicates() {
+        let mut heap = MinHeap::new();
+
+        for n in [3, 1, 3, 2, 1] {
+            heap.push(n);
+        }
+
+        assert_eq!(drain(&mut heap), vec![1, 1, 2, 3, 3]);
+    }
+
+    #[test]
+    fn peek_shows_the_minimum_without_removing_it() {
+        let mut heap = MinHeap::new();
+        assert_eq!(heap.peek(), None);
+
+        heap.push(String::from("pear"));
+        heap.push(String::from("apple"));
+        heap.push(String::from("fig"));
+
+        assert_eq!(heap.peek().map(|s| s.as_str()), Some("apple"));
+        assert_eq!(heap.len(), 3);
+    }
+
+    #[test]
+    fn pop_on_empty_is_none() {
+        let mut heap: MinHeap<i32> = MinHeap::new();
+
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    fn pushes_between_pops() {
+        let mut heap = MinHeap::new();
+        heap.push(5);
+        heap.push(3);
+
+        assert_eq!(heap.pop(), Some(3));
+        heap.push(1);
+        heap.push(4);
+
+        assert_eq!(drain(&mut heap), vec![1, 4, 5]);
+    }
+}
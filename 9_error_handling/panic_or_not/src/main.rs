@@ -1,4 +1,5 @@
 use std::net::IpAddr;
+use std::ops::{Add, Sub};
 
 pub struct Guess {
     value: i32,
@@ -19,12 +20,91 @@ impl Guess {
     }
 }
 
+// the non panicking version of the expect in main, for input we didnt write ourselves
+fn parse_ip(s: &str) -> Result<IpAddr, String> {
+    s.trim()
+        .parse()
+        .map_err(|e| format!("`{s}` is not a valid IP address: {e}"))
+}
+
+// Like Guess, the only way to get a ValidatedIp is through new, so holding one
+// means the address was already checked and nobody has to check it again
+// this one hands back an Err instead of panicking since bad input is expected here
+#[derive(Debug, PartialEq)]
+pub struct ValidatedIp(IpAddr);
+
+impl ValidatedIp {
+    pub fn new(s: &str) -> Result<ValidatedIp, String> {
+        parse_ip(s).map(ValidatedIp)
+    }
+
+    pub fn value(&self) -> IpAddr {
+        self.0
+    }
+}
+
+// Another validated type like Guess, only this time new hands back an Err for a bad value
+// Once we have a Percentage it is always between 0 and 100, adding or subtracting
+// clamps the result so that can never change
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+pub struct Percentage(f64);
+
+impl Percentage {
+    pub const MIN: f64 = 0.0;
+    pub const MAX: f64 = 100.0;
+
+    // written as a contains check so NaN is rejected too, it isnt inside any range
+    pub fn new(value: f64) -> Result<Percentage, String> {
+        if !(Percentage::MIN..=Percentage::MAX).contains(&value) {
+            return Err(format!("Percentage must be between 0 and 100, got {value}."));
+        }
+
+        Ok(Percentage(value))
+    }
+
+    pub fn value(&self) -> f64 {
+        self.0
+    }
+
+    fn clamped(value: f64) -> Percentage {
+        Percentage(value.clamp(Percentage::MIN, Percentage::MAX))
+    }
+}
+
+impl Add for Percentage {
+    type Output = Percentage;
+
+    fn add(self, other: Percentage) -> Percentage {
+        Percentage::clamped(self.0 + other.0)
+    }
+}
+
+impl Sub for Percentage {
+    type Output = Percentage;
+
+    fn sub(self, other: Percentage) -> Percentage {
+        Percentage::clamped(self.0 - other.0)
+    }
+}
+
 fn main() {
     // an example of when expect is verbose and useful
     let _home: IpAddr = "127.0.0.1"
     .parse()
     .expect("Hardcoded IP address should be valid");
 
+    // when the address comes from a user we want an error we can show them instead
+    for input in ["192.168.0.1", "::1", "999.1.1.1"] {
+        match ValidatedIp::new(input) {
+            Ok(ip) => println!("valid address {}", ip.value()),
+            Err(e) => println!("{e}"),
+        }
+    }
+
+    // adding two percentages can never go past 100
+    let battery = Percentage::new(80.0).unwrap() + Percentage::new(35.0).unwrap();
+    println!("battery is at {}%", battery.value());
+
     // lets mess around with custom types for validation
         loop {
         // --snip--
@@ -45,4 +125,62 @@ fn main() {
         //     // --snip--
         // }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    #[test]
+    fn parses_ipv4() {
+        assert_eq!(
+            parse_ip("127.0.0.1"),
+            Ok(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)))
+        );
+    }
+
+    #[test]
+    fn parses_ipv6() {
+        let ip = ValidatedIp::new("::1").unwrap();
+
+        assert_eq!(ip.value(), IpAddr::V6(Ipv6Addr::LOCALHOST));
+        assert!(ip.value().is_ipv6());
+    }
+
+    #[test]
+    fn rejects_malformed_address() {
+        let err = parse_ip("256.0.0.1").unwrap_err();
+
+        assert!(err.starts_with("`256.0.0.1` is not a valid IP address"));
+        assert!(ValidatedIp::new("not an ip").is_err());
+        assert!(ValidatedIp::new("").is_err());
+    }
+
+    #[test]
+    fn percentage_accepts_boundaries() {
+        assert_eq!(Percentage::new(0.0).map(|p| p.value()), Ok(0.0));
+        assert_eq!(Percentage::new(100.0).map(|p| p.value()), Ok(100.0));
+        assert_eq!(Percentage::new(42.5).map(|p| p.value()), Ok(42.5));
+    }
+
+    #[test]
+    fn percentage_rejects_out_of_range() {
+        assert_eq!(
+            Percentage::new(100.5),
+            Err(String::from("Percentage must be between 0 and 100, got 100.5."))
+        );
+        assert!(Percentage::new(-0.1).is_err());
+        assert!(Percentage::new(f64::NAN).is_err());
+    }
+
+    #[test]
+    fn percentage_arithmetic_clamps() {
+        let high = Percentage::new(70.0).unwrap();
+        let low = Percentage::new(40.0).unwrap();
+
+        assert_eq!((high + low).value(), 100.0);
+        assert_eq!((low - high).value(), 0.0);
+        assert_eq!((high - low).value(), 30.0);
+    }
+}
@@ -1,4 +1,4 @@
-use std::{fs::{self, File}, io::{Error, ErrorKind, Read}};
+use std::{fmt, fs::{self, File}, io::{self, Error, ErrorKind, Read}};
 
 // here is an example of us using the Result value
 // in the case that the file correctly opens we get a return value of
@@ -29,12 +29,48 @@ fn main() {
         },
     };
 
+    // that nested match is handy enough that its worth pulling into its own function
+    let _greeting_file = open_or_create("hello.txt").expect("couldn't open or create hello.txt");
+
     // using 'unwrap' we will either get back the value Ok() or the program will panic all within one line
     let greeting_file = File::open("hello.txt").unwrap();
 
     // we can also use expect to convey some message about what went wrong
     let greeting_file = File::open("hello.txt")
         .expect("hello.txt should be included in this project");
+
+    // there is no greeting.txt so this prints the default instead of failing
+    let greeting = read_with_default("greeting.txt", "Hello!").unwrap();
+    println!("{greeting}");
+
+    // with our own error type we can tell the difference between "couldnt read it"
+    // and "read it fine but there was nothing there"
+    match read_username() {
+        Ok(username) => println!("Hello, {username}!"),
+        Err(UsernameError::Empty) => println!("hello.txt has no username in it"),
+        Err(UsernameError::Io(e)) => println!("Couldn't read hello.txt: {e}"),
+    }
+}
+
+// the same open-or-create logic as in main but returning the error instead of panicking
+// only NotFound is handled here, anything else (like permissions) is passed back up
+fn open_or_create(path: &str) -> io::Result<File> {
+    match File::open(path) {
+        Ok(file) => Ok(file),
+        Err(error) => match error.kind() {
+            ErrorKind::NotFound => File::create(path),
+            _ => Err(error),
+        },
+    }
+}
+
+// a missing file isnt always an error, sometimes we just want to fall back to something
+fn read_with_default(path: &str, default: &str) -> io::Result<String> {
+    match fs::read_to_string(path) {
+        Ok(contents) => Ok(contents),
+        Err(error) if error.kind() == ErrorKind::NotFound => Ok(default.to_string()),
+        Err(error) => Err(error),
+    }
 }
 
 // lets use an example of error propagation
@@ -59,7 +95,7 @@ fn read_username_from_file() -> Result<String, Error> {
 // instead of matching every error we can simply use ?
 // another interesting thing about the ? operator is that is calls From
 // therefore we could use some cutom error type in the result and it would convert to it
-// for this to work we would need to define impl From<io::Error> for our custom error
+// for this to work we would need to define impl From<io::Error> for our custom error (see UsernameError below)
 fn read_username_from_file_2() -> Result<String, Error> {
     let mut username_file = File::open("hello.txt")?;
     let mut username = String::new();
@@ -67,6 +103,51 @@ fn read_username_from_file_2() -> Result<String, Error> {
     Ok(username)
 }
 
+// here is that custom error type in action
+// Io wraps whatever went wrong reading the file, Empty is our own extra failure case
+#[derive(Debug)]
+enum UsernameError {
+    Io(io::Error),
+    Empty,
+}
+
+impl fmt::Display for UsernameError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            UsernameError::Io(e) => write!(f, "couldn't read username: {e}"),
+            UsernameError::Empty => write!(f, "username file is empty"),
+        }
+    }
+}
+
+impl std::error::Error for UsernameError {}
+
+// this is the From impl the ? operator looks for
+impl From<io::Error> for UsernameError {
+    fn from(error: io::Error) -> Self {
+        UsernameError::Io(error)
+    }
+}
+
+fn read_username() -> Result<String, UsernameError> {
+    read_username_from("hello.txt")
+}
+
+// both ? below return an io::Error, but they get converted into UsernameError::Io for us
+fn read_username_from(path: &str) -> Result<String, UsernameError> {
+    let mut username = String::new();
+
+    File::open(path)?.read_to_string(&mut username)?;
+
+    let username = username.trim();
+
+    if username.is_empty() {
+        return Err(UsernameError::Empty);
+    }
+
+    Ok(username.to_string())
+}
+
 // here is an even more shortened version from method chaining
 fn read_username_from_file_3() -> Result<String, Error> {
     let mut username = String::new();
@@ -92,3 +173,119 @@ fn read_username_from_file_4() -> Result<String, Error> {
 fn last_char_of_first_line(text: &str) -> Option<char> {
     text.lines().next()?.chars().last()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::path::PathBuf;
+
+    // each test gets its own file in the temp dir so they dont trip over each other
+    fn temp_file(name: &str, contents: &str) -> PathBuf {
+        let path = temp_path(name);
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    fn temp_path(name: &str) -> PathBuf {
+        env::temp_dir().join(format!("recoverable_errors_{}_{name}", std::process::id()))
+    }
+
+    #[test]
+    fn open_or_create_creates_missing_file() {
+        let path = temp_path("created");
+        let _ = fs::remove_file(&path);
+
+        let file = open_or_create(path.to_str().unwrap());
+        let existed = path.exists();
+        fs::remove_file(&path).unwrap();
+
+        assert!(file.is_ok());
+        assert!(existed);
+    }
+
+    #[test]
+    fn open_or_create_opens_existing_file() {
+        let path = temp_file("existing", "already here");
+
+        let mut contents = String::new();
+        open_or_create(path.to_str().unwrap())
+            .unwrap()
+            .read_to_string(&mut contents)
+            .unwrap();
+        fs::remove_file(&path).unwrap();
+
+        // opening shouldnt have truncated the file
+        assert_eq!(contents, "already here");
+    }
+
+    #[test]
+    fn open_or_create_passes_other_errors_through() {
+        // a file inside a directory that doesnt exist cant be created either
+        let path = temp_path("no_such_dir").join("file.txt");
+
+        let error = open_or_create(path.to_str().unwrap()).unwrap_err();
+
+        assert_eq!(error.kind(), ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn read_with_default_falls_back_when_missing() {
+        let path = temp_path("missing_greeting");
+
+        let contents = read_with_default(path.to_str().unwrap(), "Hello!").unwrap();
+
+        assert_eq!(contents, "Hello!");
+        // falling back doesnt create the file
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn read_with_default_reads_existing_file() {
+        let path = temp_file("greeting", "Howdy!");
+
+        let contents = read_with_default(path.to_str().unwrap(), "Hello!");
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(contents.unwrap(), "Howdy!");
+    }
+
+    #[test]
+    fn reads_username() {
+        let path = temp_file("username", "ferris\n");
+
+        let username = read_username_from(path.to_str().unwrap());
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(username.unwrap(), "ferris");
+    }
+
+    #[test]
+    fn empty_file_is_empty_error() {
+        let path = temp_file("empty", "");
+
+        let username = read_username_from(path.to_str().unwrap());
+        fs::remove_file(&path).unwrap();
+
+        assert!(matches!(username, Err(UsernameError::Empty)));
+    }
+
+    #[test]
+    fn whitespace_file_is_empty_error() {
+        let path = temp_file("whitespace", "  \n\t \n");
+
+        let username = read_username_from(path.to_str().unwrap());
+        fs::remove_file(&path).unwrap();
+
+        assert!(matches!(username, Err(UsernameError::Empty)));
+    }
+
+    #[test]
+    fn missing_file_is_io_error() {
+        let path = env::temp_dir().join("recoverable_errors_this_file_does_not_exist");
+
+        let username = read_username_from(path.to_str().unwrap());
+
+        assert!(matches!(username, Err(UsernameError::Io(e)) if e.kind() == ErrorKind::NotFound));
+    }
+}
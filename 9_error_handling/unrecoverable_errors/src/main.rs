@@ -4,8 +4,21 @@
  * Adding this avoids rust's cleanup of the stack
 */
 
+use std::panic;
+use std::sync::{Arc, Mutex};
+
 // lets induce a panic
 fn main() {
+    // the recoverable way first, asking for index 99 gives us an Err we can print instead of a crash
+    let v = vec![1, 2, 3];
+    match safe_get(&v, 99) {
+        Ok(value) => println!("got {value}"),
+        Err(e) => println!("{e}"),
+    }
+
+    // from here on panics go through our own hook, it prints and also remembers the message
+    let _panic_log = install_logging_panic_hook();
+
     panic!("crash and burn");
 }
 
@@ -14,4 +27,84 @@ fn panic_vec() {
     let v = vec![1, 2, 3];
 
     v[99];
-}
\ No newline at end of file
+}
+
+// the recoverable version of panic_vec, slice get hands back None instead of panicking
+// so we can turn that into an error that says what went wrong
+fn safe_get<T>(v: &[T], index: usize) -> Result<&T, String> {
+    v.get(index)
+        .ok_or_else(|| format!("index {index} is out of bounds for a length of {}", v.len()))
+}
+
+// Replaces the default "thread 'main' panicked at ..." output with our own hook
+// Every panic message is pushed into the returned buffer, it is an Arc<Mutex<..>> since
+// the hook can be called from any thread. The hook runs before the stack unwinds
+// (or before the abort with panic = 'abort') so it sees every panic either way
+fn install_logging_panic_hook() -> Arc<Mutex<Vec<String>>> {
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let hook_log = Arc::clone(&log);
+
+    panic::set_hook(Box::new(move |info| {
+        // panic!("literal") gives a &str payload, panic!("{}", x) gives a String
+        let payload = info.payload();
+        let message = if let Some(s) = payload.downcast_ref::<&str>() {
+            s.to_string()
+        } else if let Some(s) = payload.downcast_ref::<String>() {
+            s.clone()
+        } else {
+            String::from("<unknown panic>")
+        };
+
+        eprintln!("logged panic: {message}");
+        hook_log.lock().unwrap().push(message);
+    }));
+
+    log
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn safe_get_in_bounds() {
+        let v = vec![1, 2, 3];
+
+        assert_eq!(safe_get(&v, 0), Ok(&1));
+        assert_eq!(safe_get(&v, 2), Ok(&3));
+    }
+
+    #[test]
+    fn safe_get_out_of_bounds_names_the_index() {
+        let v = vec![1, 2, 3];
+
+        assert_eq!(
+            safe_get(&v, 99),
+            Err(String::from("index 99 is out of bounds for a length of 3"))
+        );
+    }
+
+    #[test]
+    fn safe_get_on_empty_slice() {
+        let v: Vec<i32> = vec![];
+
+        assert!(safe_get(&v, 0).is_err());
+    }
+
+    #[test]
+    fn panic_hook_captures_the_message() {
+        // the hook is global to the whole program, so the old one is put back
+        // before we assert anything and a failed assert still goes through the normal hook
+        let previous = panic::take_hook();
+        let log = install_logging_panic_hook();
+
+        let result = panic::catch_unwind(|| {
+            panic!("captured {}", 42);
+        });
+
+        panic::set_hook(previous);
+
+        assert!(result.is_err());
+        assert_eq!(*log.lock().unwrap(), vec![String::from("captured 42")]);
+    }
+}